@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use toml;
+
+use registry::Day;
+use solution::{Answer, Part};
+
+/// Known-correct answers for one day, as recorded in the year's answers file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    /// The day takes long enough that it is skipped by default in tests.
+    pub slow: bool,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// The recorded answers for a year, read from `resources/<year>/answers.toml`.
+///
+/// Each day has its own table, e.g.
+///
+/// ```toml
+/// [day7]
+/// part1 = 46065
+/// part2 = 14134
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: BTreeMap<u32, Expected>,
+}
+
+fn to_answer(day: &str, key: &str, value: &toml::Value) -> Result<Answer, String> {
+    match *value {
+        toml::Value::Integer(n) => Ok(Answer::Number(n)),
+        toml::Value::String(ref s) => Ok(Answer::Text(s.clone())),
+        _ => Err(format!(
+            "{}.{} must be an integer or a string, found {}",
+            day,
+            key,
+            value.type_str()
+        )),
+    }
+}
+
+fn parse_expected(name: &str, table: &toml::value::Table) -> Result<Expected, String> {
+    let mut expected = Expected::default();
+
+    for (key, value) in table {
+        match key.as_ref() {
+            "part1" => expected.part1 = Some(to_answer(name, key, value)?),
+            "part2" => expected.part2 = Some(to_answer(name, key, value)?),
+            "slow" => {
+                expected.slow = value
+                    .as_bool()
+                    .ok_or_else(|| format!("{}.slow must be a boolean", name))?
+            }
+            _ => return Err(format!("unknown key {}.{}", name, key)),
+        }
+    }
+
+    Ok(expected)
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let value = text.parse::<toml::Value>().map_err(|e| e.to_string())?;
+        let tables = match value.as_table() {
+            Some(t) => t,
+            None => return Err("expected a table of days".to_owned()),
+        };
+
+        let mut days = BTreeMap::new();
+        for (name, value) in tables {
+            let day = match name.trim_start_matches("day").parse::<u32>() {
+                Ok(d) if name.starts_with("day") => d,
+                _ => return Err(format!("expected a table named day<N>, found {}", name)),
+            };
+
+            match value.as_table() {
+                Some(table) => days.insert(day, parse_expected(name, table)?),
+                None => return Err(format!("{} must be a table", name)),
+            };
+        }
+
+        Ok(Answers { days })
+    }
+
+    // A missing file just means nothing has been recorded yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let mut text = String::new();
+        File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut text))
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u32) -> Option<&Expected> {
+        self.days.get(&day)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = (u32, &Expected)> {
+        self.days.iter().map(|(&d, e)| (d, e))
+    }
+}
+
+/// Outcome of checking one part's answer against the recorded one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct(Answer),
+    Wrong {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The part produced an answer, but there is nothing recorded to check it against.
+    Unverified(Answer),
+    /// Neither an answer nor a recorded one.
+    Unsolved,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&Answer>, actual: Answer) -> Verdict {
+        match expected {
+            // Compare the printed forms, so a keypad code recorded as an
+            // integer still matches a solution which returns text
+            Some(e) if e.to_string() == actual.to_string() => Verdict::Correct(actual),
+            Some(e) => Verdict::Wrong {
+                expected: e.clone(),
+                actual,
+            },
+            None if actual.is_solved() => Verdict::Unverified(actual),
+            None => Verdict::Unsolved,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(*self, Verdict::Wrong { .. })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Verdict::Correct(ref a) => write!(f, "ok ({})", a),
+            Verdict::Wrong {
+                ref expected,
+                ref actual,
+            } => write!(f, "FAILED, expected {} but got {}", expected, actual),
+            Verdict::Unverified(ref a) => write!(f, "no recorded answer ({})", a),
+            Verdict::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Run both parts of a day against its input and check them against the
+/// recorded answers.
+pub fn verify(day: &Day, input: &str, expected: &Expected) -> Result<Vec<(Part, Verdict)>, String> {
+    let parsed = day.solution().parse(input).map_err(|e| e.to_string())?;
+
+    Ok(Part::both()
+        .into_iter()
+        .map(|part| {
+            let actual = day.solve(part, &*parsed);
+            (part, Verdict::check(expected.get(part), actual))
+        })
+        .collect())
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse(
+        "[day1]\npart1 = 232\npart2 = 1783\n\n[day4]\npart1 = 254575\nslow = true\n\n\
+         [day11]\npart1 = \"hepxxyzz\"\n\n[day22]\n",
+    )
+    .unwrap();
+
+    let day1 = answers.get(1).unwrap();
+    assert_eq!(Some(&Answer::Number(1783)), day1.get(Part::Two));
+    assert!(!day1.slow);
+    assert!(answers.get(4).unwrap().slow);
+    assert_eq!(
+        Some(&Answer::Text("hepxxyzz".to_owned())),
+        answers.get(11).unwrap().get(Part::One)
+    );
+    assert_eq!(Some(&Expected::default()), answers.get(22));
+    assert_eq!(
+        vec![1, 4, 11, 22],
+        answers.days().map(|(d, _)| d).collect::<Vec<u32>>()
+    );
+
+    assert!(Answers::parse("[day1]\npart3 = 1\n").is_err());
+    assert!(Answers::parse("[first]\npart1 = 1\n").is_err());
+    assert!(Answers::parse("[day1]\npart1 = 1.5\n").is_err());
+}
+
+#[test]
+fn test_verdict() {
+    let expected = Answer::Number(42);
+    assert_eq!(
+        Verdict::Correct(Answer::Number(42)),
+        Verdict::check(Some(&expected), Answer::Number(42))
+    );
+    assert!(Verdict::check(Some(&expected), Answer::Number(41)).is_failure());
+    assert!(Verdict::check(Some(&expected), Answer::Unsolved).is_failure());
+    assert!(!Verdict::check(Some(&expected), Answer::Text("42".to_owned())).is_failure());
+    assert_eq!(Verdict::Unsolved, Verdict::check(None, Answer::Unsolved));
+    assert!(!Verdict::check(None, Answer::Number(1)).is_failure());
+}
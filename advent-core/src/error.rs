@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// Error describing where and why a puzzle input could not be parsed.
+///
+/// Line and column numbers start at 1; a 0 means the position is not known,
+/// e.g. the column of a line which didn't match the expected format at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// What was expected instead, e.g. "a number".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new<T, E>(text: T, expected: E) -> ParseError
+    where
+        T: Into<String>,
+        E: Into<String>,
+    {
+        ParseError {
+            line: 0,
+            column: 0,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> ParseError {
+        self.line = line;
+        self.column = column;
+        self
+    }
+
+    // Set the column from where the offending text first appears in the line
+    pub fn within(mut self, line: &str) -> ParseError {
+        self.column = match line.find(self.text.as_str()) {
+            Some(idx) if !self.text.is_empty() => line[..idx].chars().count() + 1,
+            _ => 0,
+        };
+        self
+    }
+
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (0, _) => {}
+            (l, 0) => write!(f, "line {}: ", l)?,
+            (l, c) => write!(f, "line {}, column {}: ", l, c)?,
+        }
+
+        if self.text.is_empty() {
+            write!(f, "expected {}, found nothing", self.expected)
+        } else {
+            write!(f, "expected {}, found {:?}", self.expected, self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error writing out something a setting asked for, like a drawing of the
+/// input, to the file named by the setting.
+#[derive(Debug)]
+pub struct ExportError {
+    /// The name of the setting, e.g. "dot".
+    pub setting: String,
+    /// Where it was being written to.
+    pub path: String,
+    pub error: io::Error,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "could not write the {} to {}: {}",
+            self.setting, self.path, self.error
+        )
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Parse a number, with an error naming the text that isn't one.
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.parse::<T>()
+        .map_err(|_| ParseError::new(text, "a number"))
+}
+
+/// Parse each non-blank line of the input in turn, stopping at the first one
+/// which fails and recording its line number in the error.
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|&(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| parse(line).map_err(|e| e.on_line(n + 1)))
+        .collect()
+}
+
+#[test]
+fn test_parse_error_display() {
+    let err = ParseError::new("thru", "\"through\"").at(3, 14);
+    assert_eq!(
+        "line 3, column 14: expected \"through\", found \"thru\"",
+        err.to_string()
+    );
+
+    let err = ParseError::new("", "a number").on_line(2);
+    assert_eq!("line 2: expected a number, found nothing", err.to_string());
+}
+
+#[test]
+fn test_parse_lines() {
+    let input = "1 2\n\n3 x\n4 5\n";
+    let result = parse_lines(input, |line| {
+        line.split_whitespace()
+            .map(|word| parse_number::<i32>(word).map_err(|e| e.within(line)))
+            .collect::<Result<Vec<i32>, ParseError>>()
+    });
+
+    assert_eq!(Err(ParseError::new("x", "a number").at(3, 3)), result);
+    assert_eq!(
+        Ok(vec![vec![1, 2], vec![4, 5]]),
+        parse_lines("1 2\n4 5", |line| line
+            .split_whitespace()
+            .map(parse_number)
+            .collect::<Result<Vec<i32>, ParseError>>())
+    );
+}
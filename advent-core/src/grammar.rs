@@ -0,0 +1,323 @@
+// Small parser combinators for puzzle inputs, which are nearly always lines
+// in a fixed format with a few names and numbers to pick out of them.
+//
+// A parser reads from a position in a line and returns what it read along
+// with the position after it. Every parser skips the spaces in front of what
+// it reads, so a format is written as its parts in order, and a tuple of
+// parsers reads each in turn:
+//
+//     (name(), after("to", name()), after("=", number::<u32>()))
+//
+// reads "London to Dublin = 464". Errors give the column where the line
+// stopped matching and the word found there.
+
+use std::str::FromStr;
+
+use error::ParseError;
+
+/// What a parser read, and the byte position in the line after it.
+pub type Parsed<T> = Result<(T, usize), ParseError>;
+
+pub trait Parser<T> {
+    /// Read from the byte position `pos` in the line.
+    fn parse_at(&self, line: &str, pos: usize) -> Parsed<T>;
+
+    /// Turn what was read into something else.
+    fn map<U, F>(self, f: F) -> impl Parser<U>
+    where
+        Self: Sized,
+        F: Fn(T) -> U,
+    {
+        move |line: &str, pos: usize| self.parse_at(line, pos).map(|(value, pos)| (f(value), pos))
+    }
+
+    /// Read with this parser, or if it doesn't match, with the other one. If
+    /// neither matches, the error is from whichever got further.
+    fn or<P>(self, other: P) -> impl Parser<T>
+    where
+        Self: Sized,
+        P: Parser<T>,
+    {
+        move |line: &str, pos: usize| match self.parse_at(line, pos) {
+            Ok(parsed) => Ok(parsed),
+            Err(first) => other.parse_at(line, pos).map_err(|second| {
+                if second.column > first.column {
+                    second
+                } else {
+                    first
+                }
+            }),
+        }
+    }
+
+    /// Describe what the parser reads differently in its errors.
+    fn expecting<E: Into<String>>(self, expected: E) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        let expected = expected.into();
+        move |line: &str, pos: usize| {
+            self.parse_at(line, pos).map_err(|e| ParseError {
+                expected: expected.clone(),
+                ..e
+            })
+        }
+    }
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&str, usize) -> Parsed<T>,
+{
+    fn parse_at(&self, line: &str, pos: usize) -> Parsed<T> {
+        self(line, pos)
+    }
+}
+
+macro_rules! sequence {
+    ($($P:ident $T:ident $p:ident $v:ident),+) => {
+        impl<$($T, $P: Parser<$T>),+> Parser<($($T,)+)> for ($($P,)+) {
+            fn parse_at(&self, line: &str, pos: usize) -> Parsed<($($T,)+)> {
+                let ($(ref $p,)+) = *self;
+                $(let ($v, pos) = $p.parse_at(line, pos)?;)+
+                Ok((($($v,)+), pos))
+            }
+        }
+    };
+}
+
+sequence!(P1 T1 p1 v1, P2 T2 p2 v2);
+sequence!(P1 T1 p1 v1, P2 T2 p2 v2, P3 T3 p3 v3);
+sequence!(P1 T1 p1 v1, P2 T2 p2 v2, P3 T3 p3 v3, P4 T4 p4 v4);
+sequence!(P1 T1 p1 v1, P2 T2 p2 v2, P3 T3 p3 v3, P4 T4 p4 v4, P5 T5 p5 v5);
+sequence!(P1 T1 p1 v1, P2 T2 p2 v2, P3 T3 p3 v3, P4 T4 p4 v4, P5 T5 p5 v5, P6 T6 p6 v6);
+
+fn skip_spaces(line: &str, pos: usize) -> usize {
+    pos + line[pos..].len() - line[pos..].trim_start().len()
+}
+
+// The position just past the characters at `pos` which match
+fn scan<F: Fn(char) -> bool>(line: &str, pos: usize, matches: F) -> usize {
+    match line[pos..].find(|c: char| !matches(c)) {
+        Some(len) => pos + len,
+        None => line.len(),
+    }
+}
+
+// An error at the byte position in the line, showing the word or the run of
+// punctuation found there
+fn error(line: &str, pos: usize, expected: &str) -> ParseError {
+    let word = match line[pos..].chars().next() {
+        Some(c) if is_name_char(c) => scan(line, pos, is_name_char),
+        _ => scan(line, pos, |c| !c.is_whitespace() && !is_name_char(c)),
+    };
+    let word = &line[pos..word];
+    ParseError::new(word, expected).at(0, line[..pos].chars().count() + 1)
+}
+
+fn fail<T>(line: &str, pos: usize, expected: &str) -> Parsed<T> {
+    Err(error(line, pos, expected))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// A whole number, with an optional minus sign.
+pub fn number<T: FromStr>() -> impl Parser<T> {
+    |line: &str, pos: usize| {
+        let start = skip_spaces(line, pos);
+        let digits = if line[start..].starts_with('-') {
+            start + 1
+        } else {
+            start
+        };
+        let end = scan(line, digits, |c| c.is_ascii_digit());
+
+        match line[start..end].parse::<T>() {
+            Ok(value) if end > digits => Ok((value, end)),
+            _ if end > digits => Err(ParseError::new(&line[start..end], "a number")
+                .at(0, line[..start].chars().count() + 1)),
+            _ => fail(line, start, "a number"),
+        }
+    }
+}
+
+/// A name made of letters, digits and underscores, not starting with a
+/// digit.
+pub fn name() -> impl Parser<String> {
+    |line: &str, pos: usize| {
+        let start = skip_spaces(line, pos);
+        match line[start..].chars().next() {
+            Some(c) if is_name_char(c) && !c.is_ascii_digit() => {
+                let end = scan(line, start, is_name_char);
+                Ok((line[start..end].to_owned(), end))
+            }
+            _ => fail(line, start, "a name"),
+        }
+    }
+}
+
+/// One or more characters which all match, like a run of letters.
+pub fn chars<F>(expected: &'static str, matches: F) -> impl Parser<String>
+where
+    F: Fn(char) -> bool,
+{
+    move |line: &str, pos: usize| {
+        let start = skip_spaces(line, pos);
+        match scan(line, start, &matches) {
+            end if end > start => Ok((line[start..end].to_owned(), end)),
+            _ => fail(line, start, expected),
+        }
+    }
+}
+
+// The length of the keyword if the line has it at `pos`, as a whole word
+fn match_keyword(line: &str, pos: usize, keyword: &str) -> Option<usize> {
+    if !line[pos..].starts_with(keyword) {
+        return None;
+    }
+
+    // A keyword ending in a letter mustn't run on into more letters
+    let end = pos + keyword.len();
+    let last = keyword.chars().last().is_some_and(char::is_alphabetic);
+    match line[end..].chars().next() {
+        Some(c) if last && c.is_alphabetic() => None,
+        _ => Some(end),
+    }
+}
+
+/// Exactly the given text, which may contain spaces and punctuation.
+pub fn keyword(keyword: &'static str) -> impl Parser<()> {
+    move |line: &str, pos: usize| {
+        let start = skip_spaces(line, pos);
+        match match_keyword(line, start, keyword) {
+            Some(end) => Ok(((), end)),
+            None => fail(line, start, &format!("{:?}", keyword)),
+        }
+    }
+}
+
+/// Whichever of the keywords the line has, the longest if several match.
+pub fn one_of(keywords: &'static [&'static str]) -> impl Parser<&'static str> {
+    move |line: &str, pos: usize| {
+        let start = skip_spaces(line, pos);
+        let found = keywords
+            .iter()
+            .filter_map(|&keyword| match_keyword(line, start, keyword).map(|end| (keyword, end)))
+            .max_by_key(|&(_, end)| end);
+
+        match found {
+            Some((keyword, end)) => Ok((keyword, end)),
+            None => {
+                let quoted: Vec<String> = keywords.iter().map(|k| format!("{:?}", k)).collect();
+                let expected = match quoted.split_last() {
+                    Some((last, [])) => last.to_owned(),
+                    Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                    None => "nothing".to_owned(),
+                };
+                fail(line, start, &expected)
+            }
+        }
+    }
+}
+
+/// The keyword and then what the parser reads, keeping only the latter.
+pub fn after<T, P: Parser<T>>(text: &'static str, parser: P) -> impl Parser<T> {
+    (keyword(text), parser).map(|((), value)| value)
+}
+
+/// One or more of what the parser reads, with the separator between each.
+pub fn separated<T, P: Parser<T>>(parser: P, separator: &'static str) -> impl Parser<Vec<T>> {
+    move |line: &str, pos: usize| {
+        let (first, mut pos) = parser.parse_at(line, pos)?;
+        let mut items = vec![first];
+
+        let mut start = skip_spaces(line, pos);
+        while let Some(end) = match_keyword(line, start, separator) {
+            let (item, next) = parser.parse_at(line, end)?;
+            items.push(item);
+            pos = next;
+            start = skip_spaces(line, pos);
+        }
+        Ok((items, pos))
+    }
+}
+
+/// A pair of numbers like "3,4".
+pub fn coordinates<T: FromStr>() -> impl Parser<(T, T)> {
+    (number(), after(",", number()))
+}
+
+/// Read the whole line with the parser, allowing only spaces after it.
+pub fn parse_line<T, P: Parser<T>>(line: &str, parser: P) -> Result<T, ParseError> {
+    let (value, pos) = parser.parse_at(line, 0)?;
+    let end = skip_spaces(line, pos);
+
+    if end < line.len() {
+        Err(error(line, end, "the end of the line"))
+    } else {
+        Ok(value)
+    }
+}
+
+#[test]
+fn test_sequence() {
+    let distance = || (name(), after("to", name()), after("=", number::<u32>()));
+
+    assert_eq!(
+        Ok(("London".to_owned(), "Dublin".to_owned(), 464)),
+        parse_line("London to Dublin = 464", distance())
+    );
+    assert_eq!(
+        Err(ParseError::new("from", "\"to\"").at(0, 8)),
+        parse_line("London from Dublin = 464", distance())
+    );
+    assert_eq!(
+        Err(ParseError::new("x", "a number").at(0, 20)),
+        parse_line("London to Dublin = x", distance())
+    );
+    assert_eq!(
+        Err(ParseError::new("km", "the end of the line").at(0, 24)),
+        parse_line("London to Dublin = 464 km", distance())
+    );
+}
+
+#[test]
+fn test_alternatives() {
+    let command = || {
+        (
+            one_of(&["turn on", "turn off", "toggle"]),
+            coordinates::<i32>(),
+        )
+    };
+    assert_eq!(
+        Ok(("turn off", (-3, 4))),
+        parse_line("turn off -3,4", command())
+    );
+    assert_eq!(
+        Err(ParseError::new("turn", "\"turn on\", \"turn off\" or \"toggle\"").at(0, 1)),
+        parse_line("turn up 1,2", command())
+    );
+    // Keywords only match whole words
+    assert!(parse_line("turn onward 1,2", command()).is_err());
+
+    let operand = || {
+        number::<u8>()
+            .map(Some)
+            .expecting("a byte")
+            .or(name().map(|_| None))
+    };
+    assert_eq!(Ok(Some(7)), parse_line("7", operand()));
+    assert_eq!(Ok(None), parse_line("x7", operand()));
+    assert_eq!(
+        Err(ParseError::new("300", "a byte").at(0, 1)),
+        parse_line("300", operand())
+    );
+
+    let list = || separated((name(), after(":", number::<i32>())), ",");
+    assert_eq!(
+        Ok(vec![("cats".to_owned(), 7), ("trees".to_owned(), 3)]),
+        parse_line("cats: 7, trees: 3", list())
+    );
+}
@@ -0,0 +1,358 @@
+// Weighted graphs between named places, and the best way of visiting every
+// one of them exactly once, which is what the routing and seating puzzles ask
+// for.
+//
+// Names are interned as they're added, so the search only deals in node ids,
+// and it is Held-Karp: the best walk through a set of nodes which ends at one
+// of them is the best walk through the rest of the set, ending wherever makes
+// it best, then the edge from there. Working through the sets in order of
+// their bits means every smaller set is done before the sets it's part of.
+// There are n 2^n walks to keep, so it gives up on graphs of more than
+// `MAX_NODES` nodes rather than run out of memory.
+
+use std::collections::HashMap;
+use std::ops::Add;
+
+pub type NodeId = usize;
+
+/// The most nodes a graph can have for `Graph::best` to search it. The search
+/// keeps n 2^n walks, which is already some hundreds of megabytes here.
+pub const MAX_NODES: usize = 20;
+
+/// Whether an edge only goes from one node to the other, or both ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    Directed,
+    Undirected,
+}
+
+/// Whether a walk starts and ends anywhere, or comes back round to where it
+/// started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Path,
+    Tour,
+}
+
+/// Whether the best walk has the least weight or the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Min,
+    Max,
+}
+
+impl Goal {
+    fn better<W: Ord>(self, a: W, b: W) -> bool {
+        match self {
+            Goal::Min => a < b,
+            Goal::Max => a > b,
+        }
+    }
+}
+
+/// The nodes in the order a walk visits them, and its total weight. A tour
+/// goes back from the last node to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Walk<W> {
+    pub nodes: Vec<NodeId>,
+    pub weight: W,
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph<W> {
+    edges: Edges,
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    // The weight of the edge from each node to each other, if there is one
+    weights: Vec<Vec<Option<W>>>,
+}
+
+impl<W> Graph<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    pub fn new(edges: Edges) -> Graph<W> {
+        Graph {
+            edges,
+            names: vec![],
+            ids: HashMap::new(),
+            weights: vec![],
+        }
+    }
+
+    /// The node with the name, added if it isn't in the graph yet.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+        for row in &mut self.weights {
+            row.push(None);
+        }
+        self.weights.push(vec![None; id + 1]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).cloned()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Add an edge between the nodes with the names, adding them too if need
+    /// be, or change its weight if it's already there.
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: W) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.weights[from][to] = Some(weight);
+        if self.edges == Edges::Undirected {
+            self.weights[to][from] = Some(weight);
+        }
+    }
+
+    pub fn weight(&self, from: NodeId, to: NodeId) -> Option<W> {
+        self.weights[from][to]
+    }
+
+    /// The total weight of a walk through the nodes in order, or None if
+    /// there's an edge missing along the way.
+    pub fn total(&self, nodes: &[NodeId], shape: Shape) -> Option<W> {
+        let mut total = W::default();
+        for hop in nodes.windows(2) {
+            total = total + self.weight(hop[0], hop[1])?;
+        }
+        match (shape, nodes.first(), nodes.last()) {
+            (Shape::Tour, Some(&first), Some(&last)) if nodes.len() > 1 => {
+                Some(total + self.weight(last, first)?)
+            }
+            _ => Some(total),
+        }
+    }
+
+    /// The best walk visiting every node exactly once. Tours all start from
+    /// the first node added, as a tour can start anywhere round it. None if
+    /// the graph is empty or has more than `MAX_NODES` nodes, or its edges
+    /// can't join every node up.
+    pub fn best(&self, shape: Shape, goal: Goal) -> Option<Walk<W>> {
+        self.best_placed(shape, goal, &[])
+    }
+
+    /// The best walk visiting every node exactly once, with some nodes placed
+    /// at given positions along it, counting from 0. A tour with no nodes
+    /// placed starts from the first node added. None as for `best`, or if the
+    /// placements clash.
+    pub fn best_placed(
+        &self,
+        shape: Shape,
+        goal: Goal,
+        placed: &[(usize, NodeId)],
+    ) -> Option<Walk<W>> {
+        let n = self.len();
+        if n == 0 || n > MAX_NODES {
+            return None;
+        }
+
+        // A tour can be turned round to start from any of its nodes, so turn
+        // it to start from the first placed node, and back again at the end
+        let first = placed.iter().map(|&(pos, _)| pos).min().unwrap_or(0);
+        if shape == Shape::Tour && 0 < first && first < n {
+            let turned: Vec<(usize, NodeId)> = placed
+                .iter()
+                .map(|&(pos, node)| (pos - first, node))
+                .collect();
+            let mut walk = self.best_placed(shape, goal, &turned)?;
+            walk.nodes.rotate_right(first);
+            return Some(walk);
+        }
+
+        // Which node each position has to have, and where each node has to be
+        let mut at: Vec<Option<NodeId>> = vec![None; n];
+        let mut position: Vec<Option<usize>> = vec![None; n];
+        for &(pos, node) in placed {
+            if pos >= n || node >= n {
+                return None;
+            }
+            if at[pos].unwrap_or(node) != node || position[node].unwrap_or(pos) != pos {
+                return None;
+            }
+            at[pos] = Some(node);
+            position[node] = Some(pos);
+        }
+        let fits = |node: NodeId, pos: usize| {
+            at[pos].unwrap_or(node) == node && position[node].unwrap_or(pos) == pos
+        };
+
+        let starts: Vec<NodeId> = match shape {
+            Shape::Path => (0..n).filter(|&node| fits(node, 0)).collect(),
+            Shape::Tour => vec![at[0].unwrap_or(0)],
+        };
+
+        // The best weight of a walk through each set of nodes, ending at each
+        let mut best: Vec<Option<W>> = vec![None; n << n];
+        for &node in &starts {
+            best[(1 << node) * n + node] = Some(W::default());
+        }
+
+        for set in 1..1 << n {
+            for last in (0..n).filter(|&last| set & 1 << last != 0) {
+                let so_far = match best[set * n + last] {
+                    Some(so_far) => so_far,
+                    None => continue,
+                };
+                let pos = set.count_ones() as usize;
+                for next in (0..n).filter(|&next| set & 1 << next == 0 && fits(next, pos)) {
+                    if let Some(weight) = self.weights[last][next] {
+                        let slot = &mut best[(set | 1 << next) * n + next];
+                        match *slot {
+                            Some(b) if !goal.better(so_far + weight, b) => {}
+                            _ => *slot = Some(so_far + weight),
+                        }
+                    }
+                }
+            }
+        }
+
+        // The best last node, counting the way back to the start of a tour
+        let all = (1 << n) - 1;
+        let mut end = None;
+        for node in 0..n {
+            let weight = match (shape, best[all * n + node]) {
+                (_, None) => None,
+                (Shape::Tour, Some(_)) if n == 1 => best[all * n + node],
+                (Shape::Tour, Some(w)) => self.weights[node][starts[0]].map(|back| w + back),
+                (Shape::Path, Some(w)) => Some(w),
+            };
+            match (weight, end) {
+                (None, _) => {}
+                (Some(w), Some((_, b))) if !goal.better(w, b) => {}
+                (Some(w), _) => end = Some((node, w)),
+            }
+        }
+        let (mut last, weight) = end?;
+
+        // Follow the walk back, to whichever node it could have come from
+        let mut nodes = vec![last];
+        let mut set = all;
+        while set != 1 << last {
+            let rest = set ^ 1 << last;
+            let there = best[set * n + last];
+            last = (0..n)
+                .filter(|&prev| rest & 1 << prev != 0)
+                .find(|&prev| {
+                    let weight = self.weights[prev][last];
+                    best[rest * n + prev].and_then(|w| weight.map(|weight| w + weight)) == there
+                })
+                .expect("a walk to have come from somewhere");
+            nodes.push(last);
+            set = rest;
+        }
+        nodes.reverse();
+
+        Some(Walk { nodes, weight })
+    }
+}
+
+#[test]
+fn test_paths() {
+    let mut graph = Graph::new(Edges::Undirected);
+    graph.add_edge("London", "Dublin", 464);
+    graph.add_edge("London", "Belfast", 518);
+    graph.add_edge("Dublin", "Belfast", 141);
+    let names = |walk: Walk<i32>| -> Vec<String> {
+        walk.nodes
+            .iter()
+            .map(|&id| graph.name(id).to_owned())
+            .collect()
+    };
+
+    let shortest = graph.best(Shape::Path, Goal::Min).unwrap();
+    assert_eq!(605, shortest.weight);
+    assert_eq!(vec!["Belfast", "Dublin", "London"], names(shortest));
+    assert_eq!(982, graph.best(Shape::Path, Goal::Max).unwrap().weight);
+    assert_eq!(1123, graph.best(Shape::Tour, Goal::Min).unwrap().weight);
+
+    let ids: Vec<NodeId> = ["Dublin", "London", "Belfast"]
+        .iter()
+        .map(|name| graph.id(name).unwrap())
+        .collect();
+    assert_eq!(Some(982), graph.total(&ids, Shape::Path));
+
+    graph.add_edge("Paris", "Rome", 1100);
+    assert_eq!(None, graph.best(Shape::Path, Goal::Min));
+
+    // One node too many is given up on, however well joined up
+    let mut graph = Graph::new(Edges::Undirected);
+    for a in 0..=MAX_NODES {
+        for b in 0..a {
+            graph.add_edge(&a.to_string(), &b.to_string(), 1);
+        }
+    }
+    assert_eq!(None, graph.best(Shape::Path, Goal::Min));
+}
+
+#[test]
+fn test_directed_tour() {
+    // Round one way is much shorter than the other
+    let mut graph = Graph::new(Edges::Directed);
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("b", "c", 1);
+    graph.add_edge("c", "a", 1);
+    graph.add_edge("a", "c", 10);
+    graph.add_edge("c", "b", 10);
+    graph.add_edge("b", "a", 10);
+
+    let shortest = graph.best(Shape::Tour, Goal::Min).unwrap();
+    assert_eq!(
+        Walk {
+            nodes: vec![0, 1, 2],
+            weight: 3
+        },
+        shortest
+    );
+    let longest = graph.best(Shape::Tour, Goal::Max).unwrap();
+    assert_eq!(
+        Walk {
+            nodes: vec![0, 2, 1],
+            weight: 30
+        },
+        longest
+    );
+    assert_eq!(Some(2), graph.total(&[0, 1, 2], Shape::Path));
+    assert_eq!(Some(30), graph.total(&[0, 2, 1], Shape::Tour));
+
+    // With c first and b next, the only tour is the long way round
+    let placed = graph.best_placed(Shape::Tour, Goal::Min, &[(0, 2), (1, 1)]);
+    assert_eq!(
+        Some(Walk {
+            nodes: vec![2, 1, 0],
+            weight: 30
+        }),
+        placed
+    );
+    assert_eq!(
+        None,
+        graph.best_placed(Shape::Path, Goal::Min, &[(0, 2), (1, 2)])
+    );
+
+    // With only b placed, last, the short way round can still start from c
+    let placed = graph.best_placed(Shape::Tour, Goal::Min, &[(2, 1)]);
+    assert_eq!(
+        Some(Walk {
+            nodes: vec![2, 0, 1],
+            weight: 3
+        }),
+        placed
+    );
+}
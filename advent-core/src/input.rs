@@ -0,0 +1,188 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable which overrides the location of the resources tree.
+pub const RESOURCES_VAR: &str = "ADVENT_RESOURCES";
+
+/// Where the input for a day is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn read(&self) -> Result<String, String> {
+        let mut input = String::new();
+        match *self {
+            Source::File(ref path) => File::open(path)
+                .and_then(|mut f| f.read_to_string(&mut input))
+                .map_err(|e| format!("failed to read {}: {}", path.display(), e))?,
+            Source::Stdin => io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read standard input: {}", e))?,
+        };
+        Ok(input)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Source::File(ref path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "standard input"),
+        }
+    }
+}
+
+/// Finds puzzle inputs in the `resources/<year>/day<N>` tree.
+///
+/// The resources directory is, in order of preference, the one given to
+/// `with_resources`, the one named by `ADVENT_RESOURCES`, the nearest
+/// `resources` directory above the current directory, or the one in the
+/// workspace this crate was built from.
+pub struct InputLoader {
+    resources: Option<PathBuf>,
+    stdin_piped: bool,
+}
+
+impl Default for InputLoader {
+    fn default() -> InputLoader {
+        InputLoader::new()
+    }
+}
+
+impl InputLoader {
+    pub fn new() -> InputLoader {
+        InputLoader {
+            resources: env::var_os(RESOURCES_VAR).map(PathBuf::from),
+            stdin_piped: !io::stdin().is_terminal(),
+        }
+    }
+
+    pub fn with_resources<P: AsRef<Path>>(dir: P) -> InputLoader {
+        InputLoader {
+            resources: Some(dir.as_ref().to_path_buf()),
+            stdin_piped: !io::stdin().is_terminal(),
+        }
+    }
+
+    pub fn resources_dir(&self) -> Option<PathBuf> {
+        if let Some(ref dir) = self.resources {
+            return Some(dir.clone());
+        }
+
+        // Look for the nearest resources directory, so the binary works from
+        // anywhere inside the repository
+        if let Ok(cwd) = env::current_dir() {
+            for dir in cwd.ancestors() {
+                let candidate = dir.join("resources");
+                if candidate.is_dir() {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        let built_from = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        if built_from.is_dir() {
+            Some(built_from)
+        } else {
+            None
+        }
+    }
+
+    // Path of the day's input in the resources tree, whether it exists or not
+    pub fn resource_path(&self, year: u32, day: u32) -> Option<PathBuf> {
+        self.resources_dir()
+            .map(|dir| dir.join(year.to_string()).join(format!("day{}", day)))
+    }
+
+    // Path of the year's recorded answers, whether it exists or not
+    pub fn answers_path(&self, year: u32) -> Option<PathBuf> {
+        self.resources_dir()
+            .map(|dir| dir.join(year.to_string()).join("answers.toml"))
+    }
+
+    /// Work out where to read a day's input from: the resources tree if the
+    /// day has an input there, otherwise standard input if it is piped.
+    pub fn locate(&self, year: u32, day: u32) -> Result<Source, String> {
+        let path = self.resource_path(year, day);
+
+        if let Some(ref p) = path {
+            if p.is_file() {
+                return Ok(Source::File(p.clone()));
+            }
+        }
+
+        if self.stdin_piped {
+            return Ok(Source::Stdin);
+        }
+
+        match path {
+            Some(p) => Err(format!(
+                "no input for {} day {}: {} does not exist and nothing is piped to standard input",
+                year,
+                day,
+                p.display()
+            )),
+            None => Err(format!(
+                "no input for {} day {}: resources directory not found (set {}) and nothing is piped to standard input",
+                year, day, RESOURCES_VAR
+            )),
+        }
+    }
+
+    // Like `locate`, but only the resources tree is considered
+    pub fn locate_resource(&self, year: u32, day: u32) -> Result<Source, String> {
+        match self.resource_path(year, day) {
+            Some(ref p) if p.is_file() => Ok(Source::File(p.clone())),
+            Some(p) => Err(format!(
+                "no input for {} day {}: {} does not exist",
+                year,
+                day,
+                p.display()
+            )),
+            None => Err(format!(
+                "no input for {} day {}: resources directory not found (set {})",
+                year, day, RESOURCES_VAR
+            )),
+        }
+    }
+
+    pub fn load(&self, year: u32, day: u32) -> Result<String, String> {
+        self.locate(year, day)?.read()
+    }
+}
+
+#[cfg(test)]
+fn test_loader(stdin_piped: bool) -> InputLoader {
+    InputLoader {
+        resources: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources")),
+        stdin_piped,
+    }
+}
+
+#[test]
+fn test_locate_resource() {
+    let loader = test_loader(false);
+    match loader.locate(2015, 7) {
+        Ok(Source::File(path)) => assert!(path.ends_with("resources/2015/day7")),
+        other => panic!("unexpected source: {:?}", other),
+    }
+
+    assert!(loader.load(2016, 1).unwrap().starts_with("R"));
+}
+
+#[test]
+fn test_locate_missing() {
+    // Nothing piped, so a missing day is an error rather than a hang
+    let err = test_loader(false).locate(2015, 25).unwrap_err();
+    assert!(err.contains("day25"));
+
+    // Falls back to stdin when something is piped to it
+    assert_eq!(Ok(Source::Stdin), test_loader(true).locate(2015, 25));
+    assert!(test_loader(true).locate_resource(2015, 25).is_err());
+}
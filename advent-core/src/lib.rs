@@ -0,0 +1,22 @@
+// Shared building blocks for the yearly Advent of Code crates.
+//
+// Every day implements the `Solution` trait, and each year crate exposes a
+// `register` function which adds its days to a `Registry`. Tooling can then
+// enumerate and run solutions without knowing anything about a particular day.
+
+extern crate toml;
+
+mod answers;
+mod error;
+// The combinators have short, common names, so they keep their own namespace
+pub mod grammar;
+pub mod graph;
+mod input;
+mod registry;
+mod solution;
+
+pub use answers::{verify, Answers, Expected, Verdict};
+pub use error::{parse_lines, parse_number, ExportError, ParseError};
+pub use input::{InputLoader, Source, RESOURCES_VAR};
+pub use registry::{Day, Registry};
+pub use solution::{exported, Answer, DynSolution, Part, Setting, Solution};
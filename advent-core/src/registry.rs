@@ -0,0 +1,134 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use error::ParseError;
+use solution::{Answer, DynSolution, Part, Solution};
+
+/// A registered puzzle solution for a given year and day.
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    solution: Box<dyn DynSolution>,
+}
+
+impl Day {
+    pub fn solution(&self) -> &dyn DynSolution {
+        &*self.solution
+    }
+
+    // Solve the given part against input parsed by this day's solution
+    pub fn solve(&self, part: Part, input: &dyn Any) -> Answer {
+        match part {
+            Part::One => self.solution.part1(input),
+            Part::Two => self.solution.part2(input),
+        }
+    }
+
+    // Parse the input once and run both parts against it
+    pub fn run(&self, input: &str) -> Result<(Answer, Answer), ParseError> {
+        let parsed = self.solution.parse(input)?;
+        let part1 = self.solution.part1(&*parsed);
+        let part2 = self.solution.part2(&*parsed);
+        Ok((part1, part2))
+    }
+}
+
+/// Collection of all known solutions, keyed and ordered by (year, day).
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<(u32, u32), Day>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry {
+            days: BTreeMap::new(),
+        }
+    }
+
+    pub fn register<S>(&mut self, year: u32, day: u32, title: &'static str, solution: S)
+    where
+        S: Solution + 'static,
+    {
+        if self.days.contains_key(&(year, day)) {
+            panic!("{} day {} is already registered", year, day);
+        }
+
+        self.days.insert(
+            (year, day),
+            Day {
+                year,
+                day,
+                title,
+                solution: Box::new(solution),
+            },
+        );
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Day> {
+        self.days.get(&(year, day))
+    }
+
+    // All registered days, ordered by year and then by day
+    pub fn days(&self) -> impl Iterator<Item = &Day> {
+        self.days.values()
+    }
+
+    pub fn days_of(&self, year: u32) -> impl Iterator<Item = &Day> {
+        self.days.values().filter(move |d| d.year == year)
+    }
+
+    pub fn years(&self) -> Vec<u32> {
+        let mut years: Vec<u32> = self.days.keys().map(|&(y, _)| y).collect();
+        years.dedup();
+        years
+    }
+}
+
+#[cfg(test)]
+struct Length;
+
+#[cfg(test)]
+impl Solution for Length {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        input.len().into()
+    }
+}
+
+#[test]
+fn test_registry() {
+    let mut registry = Registry::new();
+    registry.register(2016, 1, "Second", Length);
+    registry.register(2015, 2, "First", Length);
+    registry.register(2015, 1, "Zeroth", Length);
+
+    assert_eq!(vec![2015, 2016], registry.years());
+    assert_eq!(2, registry.days_of(2015).count());
+
+    let order: Vec<(u32, u32)> = registry.days().map(|d| (d.year, d.day)).collect();
+    assert_eq!(vec![(2015, 1), (2015, 2), (2016, 1)], order);
+
+    let day = registry.get(2015, 2).unwrap();
+    assert_eq!("First", day.title);
+    assert_eq!(Ok((Answer::Number(3), Answer::Unsolved)), day.run("abc\n"));
+    assert!(registry.get(2017, 1).is_none());
+
+    let input = day.solution().parse("abcd").unwrap();
+    assert_eq!(Answer::Number(4), day.solve(Part::One, &*input));
+    assert_eq!(Answer::Unsolved, day.solve(Part::Two, &*input));
+}
+
+#[test]
+#[should_panic]
+fn test_duplicate_registration() {
+    let mut registry = Registry::new();
+    registry.register(2015, 1, "First", Length);
+    registry.register(2015, 1, "Again", Length);
+}
@@ -0,0 +1,282 @@
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(test)]
+use error::parse_number;
+use error::{ExportError, ParseError};
+
+/// Answer produced by one part of a puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// The part has no answer (yet), either because it isn't implemented or
+    /// because no solution could be found for the given input.
+    Unsolved,
+}
+
+impl Answer {
+    pub fn is_solved(&self) -> bool {
+        *self != Answer::Unsolved
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(ref s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Answer {
+        Answer::Text(s.to_owned())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(v: Option<T>) -> Answer {
+        match v {
+            Some(a) => a.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+/// One of the two parts of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> Vec<Part> {
+        vec![Part::One, Part::Two]
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A setting for a day given on the command line, like `guest=Yourself`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    pub name: String,
+    pub value: String,
+}
+
+impl Setting {
+    pub fn new<N, V>(name: N, value: V) -> Setting
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        Setting {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Write out what the setting asks for, to the file its value names, or
+    /// to standard output if the value is "-".
+    pub fn write(&self, contents: &[u8]) -> Result<(), ExportError> {
+        let written = if self.value == "-" {
+            io::stdout().write_all(contents)
+        } else {
+            fs::write(&self.value, contents)
+        };
+        written.map_err(|error| ExportError {
+            setting: self.name.clone(),
+            path: self.value.clone(),
+            error,
+        })
+    }
+}
+
+/// Run an export with a setting of the given name pointing at a temporary
+/// file, and return what was written to it. Meant for testing the exports of
+/// a day.
+pub fn exported<F>(name: &str, export: F) -> Vec<u8>
+where
+    F: FnOnce(&[Setting]) -> Result<(), ExportError>,
+{
+    // Tests run in parallel, so each export gets a file of its own
+    static EXPORTS: AtomicUsize = AtomicUsize::new(0);
+    let n = EXPORTS.fetch_add(1, Ordering::SeqCst);
+    let path = env::temp_dir().join(format!("advent-{}-{}-{}", name, process::id(), n));
+
+    let setting = Setting::new(name, path.to_string_lossy());
+    export(&[setting]).unwrap();
+    let contents = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    contents
+}
+
+/// A single day's puzzle.
+///
+/// Parsing is kept separate from solving so the parsed input can be shared
+/// between both parts, and so each step can be run (and measured) on its own.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Parse the input along with settings which change the puzzle. Days
+    /// without any settings reject them.
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Self::Input, ParseError> {
+        match settings.first() {
+            Some(setting) => Err(ParseError::new(setting.name.as_str(), "no settings")),
+            None => self.parse(input),
+        }
+    }
+
+    /// Write out whatever the settings ask for from the parsed input, once
+    /// it has been parsed with the same settings. Days which don't export
+    /// anything leave the settings to `parse_with`.
+    fn export(&self, _input: &Self::Input, _settings: &[Setting]) -> Result<(), ExportError> {
+        Ok(())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer;
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// Object safe version of `Solution`, so days with different input types can
+/// be stored together in a `Registry`. It is implemented for every `Solution`.
+pub trait DynSolution {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Box<dyn Any>, ParseError>;
+
+    fn export(&self, input: &dyn Any, settings: &[Setting]) -> Result<(), ExportError>;
+
+    fn part1(&self, input: &dyn Any) -> Answer;
+
+    fn part2(&self, input: &dyn Any) -> Answer;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Solution::parse(self, input).map(|i| Box::new(i) as Box<dyn Any>)
+    }
+
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Box<dyn Any>, ParseError> {
+        Solution::parse_with(self, input, settings).map(|i| Box::new(i) as Box<dyn Any>)
+    }
+
+    fn export(&self, input: &dyn Any, settings: &[Setting]) -> Result<(), ExportError> {
+        match input.downcast_ref::<S::Input>() {
+            Some(i) => Solution::export(self, i, settings),
+            None => panic!("input was not parsed by this solution"),
+        }
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer {
+        match input.downcast_ref::<S::Input>() {
+            Some(i) => Solution::part1(self, i),
+            None => panic!("input was not parsed by this solution"),
+        }
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer {
+        match input.downcast_ref::<S::Input>() {
+            Some(i) => Solution::part2(self, i),
+            None => panic!("input was not parsed by this solution"),
+        }
+    }
+}
+
+#[cfg(test)]
+struct Sum;
+
+#[cfg(test)]
+impl Solution for Sum {
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        input.split_whitespace().map(parse_number).collect()
+    }
+
+    fn part1(&self, input: &Vec<i32>) -> Answer {
+        input.iter().sum::<i32>().into()
+    }
+}
+
+#[test]
+fn test_dyn_solution() {
+    let s: &dyn DynSolution = &Sum;
+
+    let input = s.parse("1 2 3").unwrap();
+    assert_eq!(Answer::Number(6), s.part1(&*input));
+    assert_eq!(Answer::Unsolved, s.part2(&*input));
+    assert!(s.parse("1 x").is_err());
+
+    assert!(s.parse_with("1 2 3", &[]).is_ok());
+    assert_eq!(
+        Err(ParseError::new("start", "no settings")),
+        s.parse_with("1 2 3", &[Setting::new("start", "1")])
+            .map(|_| ())
+    );
+    assert!(s.export(&*input, &[]).is_ok());
+}
+
+#[test]
+fn test_setting_write() {
+    assert_eq!(
+        b"second\n".to_vec(),
+        exported("csv", |settings| settings[0].write(b"second\n"))
+    );
+
+    let nowhere = Setting::new("csv", "no/such/directory/timeline.csv");
+    let err = nowhere.write(b"second\n").unwrap_err();
+    assert_eq!("csv", err.setting);
+    assert!(err
+        .to_string()
+        .starts_with("could not write the csv to no/such/directory/timeline.csv: "));
+}
+
+#[test]
+fn test_answer_conversions() {
+    assert_eq!(Answer::Number(3), Answer::from(3usize));
+    assert_eq!(Answer::Text("abc".to_owned()), Answer::from("abc"));
+    assert_eq!(Answer::Unsolved, Answer::from(None::<i32>));
+    assert_eq!("42", format!("{}", Answer::from(Some(42))));
+}
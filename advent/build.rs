@@ -0,0 +1,52 @@
+// Generates a test for every day recorded in resources/<year>/answers.toml,
+// which tests/verify.rs includes. Slow days are generated as ignored tests.
+
+extern crate advent_core;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use advent_core::Answers;
+
+// Year directories in the resources tree, in order
+fn years(resources: &Path) -> Vec<(u32, PathBuf)> {
+    let mut years: Vec<(u32, PathBuf)> = fs::read_dir(resources)
+        .expect("failed to read the resources directory")
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.parse::<u32>().ok().map(|year| (year, entry.path()))
+        })
+        .collect();
+    years.sort();
+    years
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let resources = Path::new(&manifest_dir).join("../resources");
+    println!("cargo:rerun-if-changed={}", resources.display());
+
+    let mut tests = String::new();
+    for (year, dir) in years(&resources) {
+        let answers = Answers::load(dir.join("answers.toml")).unwrap();
+
+        for (day, expected) in answers.days() {
+            tests.push_str("#[test]\n");
+            if expected.slow {
+                tests.push_str("#[ignore]\n");
+            }
+            tests.push_str(&format!(
+                "fn verify_{}_day{:02}() {{\n    verify({}, {});\n}}\n\n",
+                year, day, year, day
+            ));
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("verify_tests.rs");
+    File::create(&out)
+        .and_then(|mut f| f.write_all(tests.as_bytes()))
+        .unwrap();
+}
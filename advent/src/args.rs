@@ -0,0 +1,426 @@
+use std::path::PathBuf;
+
+use advent_core::{Part, Setting};
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    New(NewOptions),
+    List,
+    Help,
+}
+
+// Which days of a year to run
+#[derive(Debug, PartialEq)]
+pub enum Days {
+    Single(u32),
+    All,
+}
+
+// How the answers of `run` are printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Plain,
+    Table,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub year: u32,
+    pub days: Days,
+    pub part: Option<Part>,
+    // Input file, where "-" means standard input
+    pub input: Option<PathBuf>,
+    pub resources: Option<PathBuf>,
+    pub format: Format,
+    // Settings for the day, given as --set name=value
+    pub settings: Vec<Setting>,
+}
+
+impl RunOptions {
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(p) => vec![p],
+            None => Part::both(),
+        }
+    }
+}
+
+// Which days to check against their recorded answers; everything by default
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub resources: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    // Each step is timed this many times, keeping the fastest
+    pub runs: u32,
+    pub resources: Option<PathBuf>,
+    // Where to write the results as JSON
+    pub save: Option<PathBuf>,
+    // Earlier results to compare against
+    pub compare: Option<PathBuf>,
+}
+
+// A new day to generate from the template
+#[derive(Debug, PartialEq)]
+pub struct NewOptions {
+    pub year: u32,
+    pub day: u32,
+    pub title: Option<String>,
+    // The repository to add the day to
+    pub root: Option<PathBuf>,
+}
+
+fn parse_number(flag: &str, value: Option<&String>) -> Result<u32, String> {
+    match value {
+        Some(v) => v
+            .parse::<u32>()
+            .map_err(|_| format!("invalid value for {}: {}", flag, v)),
+        None => Err(format!("missing value for {}", flag)),
+    }
+}
+
+fn parse_day(flag: &str, value: Option<&String>) -> Result<u32, String> {
+    let d = parse_number(flag, value)?;
+    if !(1..=25).contains(&d) {
+        return Err(format!("day must be between 1 and 25: {}", d));
+    }
+    Ok(d)
+}
+
+fn parse_setting(value: Option<&String>) -> Result<Setting, String> {
+    let value = match value {
+        Some(v) => v,
+        None => return Err("missing value for --set".to_owned()),
+    };
+    match value.find('=') {
+        Some(eq) if eq > 0 => Ok(Setting::new(&value[..eq], &value[eq + 1..])),
+        _ => Err(format!(
+            "invalid value for --set: {} (expected name=value)",
+            value
+        )),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<RunOptions, String> {
+    let mut year: Option<u32> = None;
+    let mut day: Option<u32> = None;
+    let mut part: Option<Part> = None;
+    let mut input: Option<PathBuf> = None;
+    let mut resources: Option<PathBuf> = None;
+    let mut format = Format::Plain;
+    let mut settings = vec![];
+    let mut all = false;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_ref() {
+            "--year" => year = Some(parse_number(arg, it.next())?),
+            "--day" => day = Some(parse_day(arg, it.next())?),
+            "--part" => {
+                part = match parse_number(arg, it.next())? {
+                    1 => Some(Part::One),
+                    2 => Some(Part::Two),
+                    p => return Err(format!("part must be 1 or 2: {}", p)),
+                }
+            }
+            "--input" => match it.next() {
+                Some(path) => input = Some(PathBuf::from(path)),
+                None => return Err("missing value for --input".to_owned()),
+            },
+            "--resources" => match it.next() {
+                Some(path) => resources = Some(PathBuf::from(path)),
+                None => return Err("missing value for --resources".to_owned()),
+            },
+            "--format" => {
+                format = match it.next().map(|f| f.as_ref()) {
+                    Some("plain") => Format::Plain,
+                    Some("table") => Format::Table,
+                    Some("json") => Format::Json,
+                    Some(f) => return Err(format!("unknown format: {}", f)),
+                    None => return Err("missing value for --format".to_owned()),
+                }
+            }
+            "--set" => settings.push(parse_setting(it.next())?),
+            "--all" => all = true,
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+
+    let year = match year {
+        Some(y) => y,
+        None => return Err("--year is required".to_owned()),
+    };
+
+    let days = match (day, all) {
+        (Some(d), false) => Days::Single(d),
+        (None, true) => Days::All,
+        (Some(_), true) => return Err("--day and --all cannot be used together".to_owned()),
+        (None, false) => return Err("either --day or --all is required".to_owned()),
+    };
+
+    if days == Days::All && input.is_some() {
+        return Err("--input can only be used with a single --day".to_owned());
+    }
+    if days == Days::All && !settings.is_empty() {
+        return Err("--set can only be used with a single --day".to_owned());
+    }
+
+    Ok(RunOptions {
+        year,
+        days,
+        part,
+        input,
+        resources,
+        format,
+        settings,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut opts = VerifyOptions {
+        year: None,
+        day: None,
+        resources: None,
+    };
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_ref() {
+            "--year" => opts.year = Some(parse_number(arg, it.next())?),
+            "--day" => opts.day = Some(parse_day(arg, it.next())?),
+            "--resources" => match it.next() {
+                Some(path) => opts.resources = Some(PathBuf::from(path)),
+                None => return Err("missing value for --resources".to_owned()),
+            },
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+
+    if opts.day.is_some() && opts.year.is_none() {
+        return Err("--day requires --year".to_owned());
+    }
+
+    Ok(opts)
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchOptions, String> {
+    let mut opts = BenchOptions {
+        year: None,
+        day: None,
+        runs: 1,
+        resources: None,
+        save: None,
+        compare: None,
+    };
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_ref() {
+            "--year" => opts.year = Some(parse_number(arg, it.next())?),
+            "--day" => opts.day = Some(parse_day(arg, it.next())?),
+            "--runs" => {
+                opts.runs = parse_number(arg, it.next())?;
+                if opts.runs == 0 {
+                    return Err("--runs must be at least 1".to_owned());
+                }
+            }
+            "--resources" | "--save" | "--compare" => {
+                let path = match it.next() {
+                    Some(p) => PathBuf::from(p),
+                    None => return Err(format!("missing value for {}", arg)),
+                };
+                match arg.as_ref() {
+                    "--resources" => opts.resources = Some(path),
+                    "--save" => opts.save = Some(path),
+                    _ => opts.compare = Some(path),
+                }
+            }
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+
+    if opts.day.is_some() && opts.year.is_none() {
+        return Err("--day requires --year".to_owned());
+    }
+
+    Ok(opts)
+}
+
+fn parse_new(args: &[String]) -> Result<NewOptions, String> {
+    let mut year: Option<u32> = None;
+    let mut day: Option<u32> = None;
+    let mut title: Option<String> = None;
+    let mut root: Option<PathBuf> = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_ref() {
+            "--year" => year = Some(parse_number(arg, it.next())?),
+            "--day" => day = Some(parse_day(arg, it.next())?),
+            "--title" => match it.next() {
+                Some(t) => title = Some(t.to_owned()),
+                None => return Err("missing value for --title".to_owned()),
+            },
+            "--root" => match it.next() {
+                Some(path) => root = Some(PathBuf::from(path)),
+                None => return Err("missing value for --root".to_owned()),
+            },
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+
+    match (year, day) {
+        (Some(year), Some(day)) => Ok(NewOptions {
+            year,
+            day,
+            title,
+            root,
+        }),
+        _ => Err("new requires --year and --day".to_owned()),
+    }
+}
+
+// Parse the command line arguments, excluding the program name
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.split_first() {
+        Some((cmd, rest)) => match cmd.as_ref() {
+            "run" => parse_run(rest).map(Command::Run),
+            "verify" => parse_verify(rest).map(Command::Verify),
+            "bench" => parse_bench(rest).map(Command::Bench),
+            "new" => parse_new(rest).map(Command::New),
+            "list" => {
+                if rest.is_empty() {
+                    Ok(Command::List)
+                } else {
+                    Err(format!("unexpected arguments to list: {}", rest.join(" ")))
+                }
+            }
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            _ => Err(format!("unknown command: {}", cmd)),
+        },
+        None => Ok(Command::Help),
+    }
+}
+
+#[cfg(test)]
+fn to_args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(|a| a.to_owned()).collect()
+}
+
+#[test]
+fn test_parse_run() {
+    assert_eq!(
+        Ok(Command::Run(RunOptions {
+            year: 2015,
+            days: Days::Single(7),
+            part: Some(Part::Two),
+            input: Some(PathBuf::from("day7.txt")),
+            resources: None,
+            format: Format::Plain,
+            settings: vec![],
+        })),
+        parse_args(&to_args(
+            "run --year 2015 --day 7 --part 2 --input day7.txt"
+        ))
+    );
+
+    assert_eq!(
+        Ok(Command::Run(RunOptions {
+            year: 2016,
+            days: Days::All,
+            part: None,
+            input: None,
+            resources: Some(PathBuf::from("/tmp/res")),
+            format: Format::Json,
+            settings: vec![],
+        })),
+        parse_args(&to_args(
+            "run --all --year 2016 --resources /tmp/res --format json"
+        ))
+    );
+
+    assert_eq!(
+        Ok(Command::Run(RunOptions {
+            year: 2015,
+            days: Days::Single(13),
+            part: None,
+            input: None,
+            resources: None,
+            format: Format::Plain,
+            settings: vec![
+                Setting::new("guest", "Yourself"),
+                Setting::new("seat", "Alice:0"),
+            ],
+        })),
+        parse_args(&to_args(
+            "run --year 2015 --day 13 --set guest=Yourself --set seat=Alice:0"
+        ))
+    );
+}
+
+#[test]
+fn test_parse_invalid_run() {
+    assert!(parse_args(&to_args("run --day 7")).is_err());
+    assert!(parse_args(&to_args("run --year 2015")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 7 --all")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 26")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 1 --part 3")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --all --input x")).is_err());
+    assert!(parse_args(&to_args("run --year")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 1 --verbose")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 1 --format xml")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --day 1 --set guest")).is_err());
+    assert!(parse_args(&to_args("run --year 2015 --all --set guest=me")).is_err());
+}
+
+#[test]
+fn test_parse_commands() {
+    assert_eq!(Ok(Command::List), parse_args(&to_args("list")));
+    assert_eq!(Ok(Command::Help), parse_args(&[]));
+    assert_eq!(Ok(Command::Help), parse_args(&to_args("--help")));
+    assert_eq!(
+        Ok(Command::Verify(VerifyOptions {
+            year: Some(2015),
+            day: None,
+            resources: None,
+        })),
+        parse_args(&to_args("verify --year 2015"))
+    );
+    assert!(parse_args(&to_args("verify --day 3")).is_err());
+    assert_eq!(
+        Ok(Command::Bench(BenchOptions {
+            year: Some(2015),
+            day: Some(4),
+            runs: 3,
+            resources: None,
+            save: Some(PathBuf::from("after.json")),
+            compare: Some(PathBuf::from("before.json")),
+        })),
+        parse_args(&to_args(
+            "bench --year 2015 --day 4 --runs 3 --compare before.json --save after.json"
+        ))
+    );
+    assert!(parse_args(&to_args("bench --runs 0")).is_err());
+    assert_eq!(
+        Ok(Command::New(NewOptions {
+            year: 2017,
+            day: 2,
+            title: Some("Checksum".to_owned()),
+            root: None,
+        })),
+        parse_args(&to_args("new --year 2017 --day 2 --title Checksum"))
+    );
+    assert!(parse_args(&to_args("new --year 2017")).is_err());
+    assert!(parse_args(&to_args("list 2015")).is_err());
+    assert!(parse_args(&to_args("solve")).is_err());
+}
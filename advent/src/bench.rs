@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::{self, Value};
+
+use advent_core::{Day, Part};
+
+// How long parsing and each part of a day took, the best of a number of runs.
+// A part which is not solved has no time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+// Run `f` the given number of times, returning its result and the
+// shortest time it took
+fn best_of<T, F>(runs: u32, mut f: F) -> Result<(T, Duration), String>
+where
+    F: FnMut() -> Result<T, String>,
+{
+    let mut best: Option<(T, Duration)> = None;
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = f()?;
+        let elapsed = start.elapsed();
+
+        best = match best {
+            Some((_, b)) if b < elapsed => Some((result, b)),
+            _ => Some((result, elapsed)),
+        };
+    }
+
+    Ok(best.unwrap())
+}
+
+pub fn time_day(day: &Day, input: &str, runs: u32) -> Result<Timing, String> {
+    let (parsed, parse) = best_of(runs, || {
+        day.solution().parse(input).map_err(|e| e.to_string())
+    })?;
+
+    let time_part = |part| {
+        best_of(runs, || Ok(day.solve(part, &*parsed)))
+            .ok()
+            .and_then(|(answer, t)| if answer.is_solved() { Some(t) } else { None })
+    };
+
+    Ok(Timing {
+        year: day.year,
+        day: day.day,
+        parse,
+        part1: time_part(Part::One),
+        part2: time_part(Part::Two),
+    })
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{}ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+fn format_part(d: Option<Duration>) -> String {
+    d.map_or("-".to_owned(), format_duration)
+}
+
+// Relative change against an earlier total, e.g. "-12.5%"
+fn format_change(before: Duration, after: Duration) -> String {
+    if before.as_nanos() == 0 {
+        return "-".to_owned();
+    }
+
+    let change = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+    format!("{:+.1}%", change)
+}
+
+pub fn print_table(timings: &[Timing], baseline: Option<&BTreeMap<(u32, u32), Timing>>) {
+    print!(
+        "{:>4} {:>3} {:>10} {:>10} {:>10} {:>10}",
+        "Year", "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+    if baseline.is_some() {
+        print!(" {:>10} {:>8}", "Before", "Change");
+    }
+    println!();
+
+    for t in timings {
+        print!(
+            "{:>4} {:>3} {:>10} {:>10} {:>10} {:>10}",
+            t.year,
+            t.day,
+            format_duration(t.parse),
+            format_part(t.part1),
+            format_part(t.part2),
+            format_duration(t.total())
+        );
+
+        if let Some(baseline) = baseline {
+            match baseline.get(&(t.year, t.day)) {
+                Some(b) => print!(
+                    " {:>10} {:>8}",
+                    format_duration(b.total()),
+                    format_change(b.total(), t.total())
+                ),
+                None => print!(" {:>10} {:>8}", "-", "-"),
+            }
+        }
+        println!();
+    }
+
+    let total = timings.iter().map(|t| t.total()).sum();
+    print!(
+        "{:>4} {:>3} {:>10} {:>10} {:>10} {:>10}",
+        "",
+        "",
+        "",
+        "",
+        "",
+        format_duration(total)
+    );
+    if let Some(baseline) = baseline {
+        // Only compare against the days which were timed both times
+        let before: Duration = timings
+            .iter()
+            .filter_map(|t| baseline.get(&(t.year, t.day)))
+            .map(|b| b.total())
+            .sum();
+        print!(
+            " {:>10} {:>8}",
+            format_duration(before),
+            format_change(before, total)
+        );
+    }
+    println!();
+}
+
+fn nanos(d: Option<Duration>) -> Value {
+    match d {
+        Some(d) => Value::from(d.as_nanos() as u64),
+        None => Value::Null,
+    }
+}
+
+pub fn to_json(timings: &[Timing]) -> Value {
+    Value::Array(
+        timings
+            .iter()
+            .map(|t| {
+                json!({
+                    "year": t.year,
+                    "day": t.day,
+                    "parse_ns": nanos(Some(t.parse)),
+                    "part1_ns": nanos(t.part1),
+                    "part2_ns": nanos(t.part2),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn from_json(value: &Value) -> Result<Vec<Timing>, String> {
+    let entries = match value.as_array() {
+        Some(e) => e,
+        None => return Err("expected an array of timings".to_owned()),
+    };
+
+    let number = |entry: &Value, key: &str| {
+        entry[key]
+            .as_u64()
+            .ok_or_else(|| format!("missing or invalid {} in {}", key, entry))
+    };
+    let duration = |entry: &Value, key: &str| match entry[key] {
+        Value::Null => Ok(None),
+        _ => number(entry, key).map(|ns| Some(Duration::from_nanos(ns))),
+    };
+
+    entries
+        .iter()
+        .map(|entry| {
+            Ok(Timing {
+                year: number(entry, "year")? as u32,
+                day: number(entry, "day")? as u32,
+                parse: Duration::from_nanos(number(entry, "parse_ns")?),
+                part1: duration(entry, "part1_ns")?,
+                part2: duration(entry, "part2_ns")?,
+            })
+        })
+        .collect()
+}
+
+pub fn save(path: &Path, timings: &[Timing]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&to_json(timings)).unwrap();
+    File::create(path)
+        .and_then(|mut f| writeln!(f, "{}", json))
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+// Earlier results, keyed by year and day
+pub fn load(path: &Path) -> Result<BTreeMap<(u32, u32), Timing>, String> {
+    let mut text = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut text))
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+    let value: Value =
+        serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let timings = from_json(&value).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(timings.into_iter().map(|t| ((t.year, t.day), t)).collect())
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!("512ns", format_duration(Duration::from_nanos(512)));
+    assert_eq!("12.3µs", format_duration(Duration::from_nanos(12_345)));
+    assert_eq!("4.5ms", format_duration(Duration::from_micros(4_500)));
+    assert_eq!("2.25s", format_duration(Duration::from_millis(2_250)));
+    assert_eq!(
+        "+50.0%",
+        format_change(Duration::from_millis(2), Duration::from_millis(3))
+    );
+    assert_eq!(
+        "-25.0%",
+        format_change(Duration::from_millis(4), Duration::from_millis(3))
+    );
+}
+
+#[test]
+fn test_json_round_trip() {
+    let timings = vec![
+        Timing {
+            year: 2015,
+            day: 4,
+            parse: Duration::from_nanos(1_200),
+            part1: Some(Duration::from_millis(340)),
+            part2: None,
+        },
+        Timing {
+            year: 2016,
+            day: 1,
+            parse: Duration::from_micros(15),
+            part1: Some(Duration::from_micros(80)),
+            part2: Some(Duration::from_micros(95)),
+        },
+    ];
+
+    assert_eq!(Ok(timings.clone()), from_json(&to_json(&timings)));
+    assert_eq!(Duration::from_micros(190), timings[1].total());
+    assert!(from_json(&json!([{ "year": 2015 }])).is_err());
+}
@@ -0,0 +1,330 @@
+extern crate advent15;
+extern crate advent16;
+extern crate advent17;
+extern crate advent_core;
+extern crate env_logger;
+#[macro_use]
+extern crate serde_json;
+
+mod args;
+mod bench;
+mod output;
+mod scaffold;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use advent_core::{Answers, Day, InputLoader, Part, Registry, Source, Verdict};
+use args::{BenchOptions, Command, Days, NewOptions, RunOptions, VerifyOptions};
+use output::Solved;
+
+fn print_usage(prog: &str) {
+    println!(
+        "usage: {} run --year <year> --day <day> [--part <1|2>] [--input <file|->] [--format <f>]",
+        prog
+    );
+    println!("           [--set <name=value>]...");
+    println!(
+        "       {} run --year <year> --all [--part <1|2>] [--format <f>]",
+        prog
+    );
+    println!(
+        "       {} verify [--year <year> [--day <day>]] [--resources <dir>]",
+        prog
+    );
+    println!(
+        "       {} bench [--year <year> [--day <day>]] [--runs <n>] [--save <file>] [--compare <file>]",
+        prog
+    );
+    println!(
+        "       {} new --year <year> --day <day> [--title <title>] [--root <dir>]",
+        prog
+    );
+    println!("       {} list", prog);
+    println!();
+    println!("The answers of run are printed in the given --format: plain (the default),");
+    println!("table or json. Diagnostics are logged, and shown by setting RUST_LOG.");
+    println!("Some days take settings which change the puzzle, each given with --set.");
+    println!();
+    println!("new writes the module of a day from a template, registers it with its");
+    println!("year and adds empty input and answers for it, without overwriting anything.");
+    println!();
+    println!("Inputs are read from resources/<year>/day<N>, or from standard input if");
+    println!("it is piped and there is no such file. The resources directory can be");
+    println!(
+        "set with --resources <dir> or the {} variable.",
+        advent_core::RESOURCES_VAR
+    );
+}
+
+// All the known solutions, from every year
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    advent15::register(&mut registry);
+    advent16::register(&mut registry);
+    advent17::register(&mut registry);
+    registry
+}
+
+fn input_loader(resources: &Option<PathBuf>) -> InputLoader {
+    match *resources {
+        Some(ref dir) => InputLoader::with_resources(dir),
+        None => InputLoader::new(),
+    }
+}
+
+// Where to read a day's input from, given the command line options
+fn input_source(loader: &InputLoader, opts: &RunOptions, day: &Day) -> Result<Source, String> {
+    match opts.input {
+        Some(ref path) if path == Path::new("-") => Ok(Source::Stdin),
+        Some(ref path) => Ok(Source::File(path.clone())),
+        // Standard input can only be read once, so it is not an option for --all
+        None => match opts.days {
+            Days::Single(_) => loader.locate(day.year, day.day),
+            Days::All => loader.locate_resource(day.year, day.day),
+        },
+    }
+}
+
+fn run_day<'a>(day: &'a Day, source: &Source, opts: &RunOptions) -> Result<Solved<'a>, String> {
+    let input = source.read()?;
+    let parsed = day
+        .solution()
+        .parse_with(&input, &opts.settings)
+        .map_err(|e| format!("{}: {}", source, e))?;
+    day.solution()
+        .export(&*parsed, &opts.settings)
+        .map_err(|e| e.to_string())?;
+    let answers = opts
+        .parts()
+        .iter()
+        .map(|&part| (part, day.solve(part, &*parsed)))
+        .collect();
+
+    Ok(Solved { day, answers })
+}
+
+// Run all the requested days, returning false if any of them failed
+fn run(registry: &Registry, opts: &RunOptions) -> bool {
+    let days: Vec<&Day> = match opts.days {
+        Days::Single(d) => registry.get(opts.year, d).into_iter().collect(),
+        Days::All => registry.days_of(opts.year).collect(),
+    };
+
+    if days.is_empty() {
+        match opts.days {
+            Days::Single(d) => eprintln!("error: no solution for {} day {}", opts.year, d),
+            Days::All => eprintln!("error: no solutions for {}", opts.year),
+        }
+        return false;
+    }
+
+    let loader = input_loader(&opts.resources);
+
+    let mut ok = true;
+    let mut results = vec![];
+    for day in days {
+        let result =
+            input_source(&loader, opts, day).and_then(|source| run_day(day, &source, opts));
+
+        match result {
+            Ok(solved) => results.push(solved),
+            Err(msg) => {
+                eprintln!("error: {} day {}: {}", day.year, day.day, msg);
+                ok = false;
+            }
+        }
+    }
+
+    output::print(opts.format, &results);
+    ok
+}
+
+// Registered days, optionally limited to a year or a single day of it
+fn select_days(registry: &Registry, year: Option<u32>, day: Option<u32>) -> Vec<&Day> {
+    registry
+        .days()
+        .filter(|d| year.is_none() || year == Some(d.year))
+        .filter(|d| day.is_none() || day == Some(d.day))
+        .collect()
+}
+
+fn verify_day(loader: &InputLoader, day: &Day) -> Result<Vec<(Part, Verdict)>, String> {
+    let answers = match loader.answers_path(day.year) {
+        Some(path) => Answers::load(path)?,
+        None => Answers::default(),
+    };
+    let expected = answers.get(day.day).cloned().unwrap_or_default();
+    let source = loader.locate_resource(day.year, day.day)?;
+    let input = source.read()?;
+
+    advent_core::verify(day, &input, &expected).map_err(|e| format!("{}: {}", source, e))
+}
+
+// Check days against their recorded answers, returning false if any of them
+// gave a different answer or failed to run
+fn verify(registry: &Registry, opts: &VerifyOptions) -> bool {
+    let days = select_days(registry, opts.year, opts.day);
+
+    if days.is_empty() {
+        eprintln!("error: no solutions to verify");
+        return false;
+    }
+
+    let loader = input_loader(&opts.resources);
+    let (mut correct, mut failed, mut unverified) = (0, 0, 0);
+
+    for day in days {
+        match verify_day(&loader, day) {
+            Ok(verdicts) => {
+                for (part, verdict) in verdicts {
+                    match verdict {
+                        Verdict::Correct(_) => correct += 1,
+                        Verdict::Wrong { .. } => failed += 1,
+                        Verdict::Unverified(_) => unverified += 1,
+                        Verdict::Unsolved => {}
+                    }
+                    println!("{} day {} part {}: {}", day.year, day.day, part, verdict);
+                }
+            }
+            Err(msg) => {
+                eprintln!("error: {} day {}: {}", day.year, day.day, msg);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} correct, {} failed, {} without a recorded answer",
+        correct, failed, unverified
+    );
+
+    failed == 0
+}
+
+// Time every selected day, returning false if any of them failed to run
+fn bench(registry: &Registry, opts: &BenchOptions) -> bool {
+    let days = select_days(registry, opts.year, opts.day);
+
+    if days.is_empty() {
+        eprintln!("error: no solutions to benchmark");
+        return false;
+    }
+
+    let baseline = match opts.compare {
+        Some(ref path) => match bench::load(path) {
+            Ok(b) => Some(b),
+            Err(msg) => {
+                eprintln!("error: {}", msg);
+                return false;
+            }
+        },
+        None => None,
+    };
+
+    let loader = input_loader(&opts.resources);
+    let mut ok = true;
+    let mut timings = vec![];
+
+    for day in days {
+        let result = loader
+            .locate_resource(day.year, day.day)
+            .and_then(|source| source.read())
+            .and_then(|input| bench::time_day(day, &input, opts.runs));
+
+        match result {
+            Ok(t) => timings.push(t),
+            Err(msg) => {
+                eprintln!("error: {} day {}: {}", day.year, day.day, msg);
+                ok = false;
+            }
+        }
+    }
+
+    bench::print_table(&timings, baseline.as_ref());
+
+    if let Some(ref path) = opts.save {
+        if let Err(msg) = bench::save(path, &timings) {
+            eprintln!("error: {}", msg);
+            ok = false;
+        }
+    }
+
+    ok
+}
+
+// Generate the module, registration and resource stubs of a new day
+fn new_day(opts: &NewOptions) -> bool {
+    let root = match opts.root.clone().or_else(scaffold::find_root) {
+        Some(root) => root,
+        None => {
+            eprintln!("error: no workspace found; pass its directory with --root");
+            return false;
+        }
+    };
+    let title = opts
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", opts.day));
+
+    match scaffold::create(&root, opts.year, opts.day, &title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+            true
+        }
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            false
+        }
+    }
+}
+
+fn list(registry: &Registry) {
+    for day in registry.days() {
+        println!("{} {:>2}  {}", day.year, day.day, day.title);
+    }
+}
+
+pub fn main() {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().collect();
+    let command = match args::parse_args(&args[1..]) {
+        Ok(cmd) => cmd,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            print_usage(&args[0]);
+            process::exit(2);
+        }
+    };
+
+    let registry = registry();
+    match command {
+        Command::Run(ref opts) => {
+            if !run(&registry, opts) {
+                process::exit(1);
+            }
+        }
+        Command::Verify(ref opts) => {
+            if !verify(&registry, opts) {
+                process::exit(1);
+            }
+        }
+        Command::Bench(ref opts) => {
+            if !bench(&registry, opts) {
+                process::exit(1);
+            }
+        }
+        Command::New(ref opts) => {
+            if !new_day(opts) {
+                process::exit(1);
+            }
+        }
+        Command::List => list(&registry),
+        Command::Help => print_usage(&args[0]),
+    }
+}
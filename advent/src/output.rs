@@ -0,0 +1,153 @@
+use serde_json::{self, Value};
+
+use advent_core::{Answer, Day, Part};
+use args::Format;
+
+// The answers produced for one day
+pub struct Solved<'a> {
+    pub day: &'a Day,
+    pub answers: Vec<(Part, Answer)>,
+}
+
+fn answer_to_json(answer: &Answer) -> Value {
+    match *answer {
+        Answer::Number(n) => Value::from(n),
+        Answer::Text(ref s) => Value::from(s.clone()),
+        Answer::Unsolved => Value::Null,
+    }
+}
+
+pub fn to_json(results: &[Solved]) -> Value {
+    Value::Array(
+        results
+            .iter()
+            .map(|r| {
+                let mut obj = json!({
+                    "year": r.day.year,
+                    "day": r.day.day,
+                    "title": r.day.title,
+                });
+                for &(part, ref answer) in &r.answers {
+                    obj[format!("part{}", part)] = answer_to_json(answer);
+                }
+                obj
+            })
+            .collect(),
+    )
+}
+
+fn print_plain(results: &[Solved]) {
+    for r in results {
+        println!("{} day {}: {}", r.day.year, r.day.day, r.day.title);
+        for &(part, ref answer) in &r.answers {
+            println!("  Part {}: {}", part, answer);
+        }
+    }
+}
+
+fn print_table(results: &[Solved]) {
+    let parts: Vec<Part> = match results.first() {
+        Some(r) => r.answers.iter().map(|&(p, _)| p).collect(),
+        None => return,
+    };
+
+    let title_width = results
+        .iter()
+        .map(|r| r.day.title.chars().count())
+        .chain(Some("Title".len()))
+        .max()
+        .unwrap();
+    let answer_width = results
+        .iter()
+        .flat_map(|r| r.answers.iter().map(|(_, a)| a.to_string().len()))
+        .chain(Some("Part 1".len()))
+        .max()
+        .unwrap();
+
+    let mut header = format!(
+        "{:>4} {:>3}  {:<tw$}",
+        "Year",
+        "Day",
+        "Title",
+        tw = title_width
+    );
+    for part in &parts {
+        header += &format!("  {:<aw$}", format!("Part {}", part), aw = answer_width);
+    }
+    println!("{}", header.trim_end());
+
+    for r in results {
+        let mut row = format!(
+            "{:>4} {:>3}  {:<tw$}",
+            r.day.year,
+            r.day.day,
+            r.day.title,
+            tw = title_width
+        );
+        for (_, answer) in &r.answers {
+            row += &format!("  {:<aw$}", answer.to_string(), aw = answer_width);
+        }
+        println!("{}", row.trim_end());
+    }
+}
+
+pub fn print(format: Format, results: &[Solved]) {
+    match format {
+        Format::Plain => print_plain(results),
+        Format::Table => print_table(results),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&to_json(results)).unwrap()
+        ),
+    }
+}
+
+#[test]
+fn test_to_json() {
+    use advent_core::{ParseError, Registry, Solution};
+
+    struct Nothing;
+
+    impl Solution for Nothing {
+        type Input = ();
+
+        fn parse(&self, _input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &()) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    let mut registry = Registry::new();
+    registry.register(2015, 3, "Counting Words", Nothing);
+    let day = registry.get(2015, 3).unwrap();
+
+    let results = vec![Solved {
+        day,
+        answers: vec![
+            (Part::One, Answer::Number(2)),
+            (Part::Two, Answer::Text("hello".to_owned())),
+        ],
+    }];
+    assert_eq!(
+        json!([{
+            "year": 2015,
+            "day": 3,
+            "title": "Counting Words",
+            "part1": 2,
+            "part2": "hello",
+        }]),
+        to_json(&results)
+    );
+
+    let results = vec![Solved {
+        day,
+        answers: vec![(Part::Two, Answer::Unsolved)],
+    }];
+    assert_eq!(
+        json!([{ "year": 2015, "day": 3, "title": "Counting Words", "part2": null }]),
+        to_json(&results)
+    );
+}
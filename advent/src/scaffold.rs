@@ -0,0 +1,280 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+// The repository holding the year crates: the nearest directory upwards with
+// a workspace manifest, or the one this binary was built from
+pub fn find_root() -> Option<PathBuf> {
+    let is_root = |dir: &Path| {
+        read(&dir.join("Cargo.toml"))
+            .map(|manifest| manifest.contains("[workspace]"))
+            .unwrap_or(false)
+    };
+
+    if let Ok(cwd) = env::current_dir() {
+        if let Some(dir) = cwd.ancestors().find(|dir| is_root(dir)) {
+            return Some(dir.to_path_buf());
+        }
+    }
+
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .filter(|dir| is_root(dir))
+        .map(|dir| dir.to_path_buf())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    let mut text = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut text))
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Ok(text)
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    File::create(path)
+        .and_then(|mut f| f.write_all(text.as_bytes()))
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+pub fn render(year: u32, day: u32, title: &str) -> String {
+    TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", title)
+}
+
+// The day of a `mod dayN;` line
+fn mod_day(line: &str) -> Option<u32> {
+    let line = line.trim();
+    if line.starts_with("mod day") && line.ends_with(';') {
+        line["mod day".len()..line.len() - 1].parse().ok()
+    } else {
+        None
+    }
+}
+
+// The day of a `registry.register(year, N, ...)` line
+fn registered_day(line: &str) -> Option<u32> {
+    let line = line.trim();
+    if !line.starts_with("registry.register(") {
+        return None;
+    }
+    line.split(',').nth(1).and_then(|d| d.trim().parse().ok())
+}
+
+// Insert `line` before the first of `lines` whose day (as found by `day_of`)
+// is later than `day`, or after the last one with a day at all
+fn insert_ordered<F>(lines: &mut Vec<String>, day: u32, line: String, day_of: F) -> bool
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| day_of(l).map(|d| (i, d)))
+        .collect();
+
+    let at = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => match days.last() {
+            Some(&(i, _)) => i + 1,
+            None => return false,
+        },
+    };
+    lines.insert(at, line);
+    true
+}
+
+// Add `mod dayN;` to the source of a year crate's lib.rs, keeping the
+// modules in order
+pub fn add_module(lib: &str, day: u32) -> Result<String, String> {
+    let mut lines: Vec<String> = lib.lines().map(|l| l.to_owned()).collect();
+    if lines.iter().any(|l| mod_day(l) == Some(day)) {
+        return Err(format!("day{} is already declared as a module", day));
+    }
+
+    let line = format!("mod day{};", day);
+    if !insert_ordered(&mut lines, day, line.clone(), mod_day) {
+        // The first module goes just before the imports
+        match lines.iter().position(|l| l.starts_with("use ")) {
+            Some(i) => {
+                lines.insert(i, String::new());
+                lines.insert(i, line);
+            }
+            None => return Err("no place to declare the module in lib.rs".to_owned()),
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Add the day to the `register` function of a year crate's lib.rs
+pub fn add_registration(lib: &str, year: u32, day: u32, title: &str) -> Result<String, String> {
+    let line = format!(
+        "    registry.register({}, {}, {:?}, day{}::Day{});",
+        year, day, title, day, day
+    );
+
+    let mut lines: Vec<String> = lib.lines().map(|l| l.to_owned()).collect();
+    if lines.iter().any(|l| registered_day(l) == Some(day)) {
+        return Err(format!("day {} is already registered", day));
+    }
+
+    if !insert_ordered(&mut lines, day, line.clone(), registered_day) {
+        // An empty register function, which doesn't use its argument yet
+        let empty = "pub fn register(_registry: &mut Registry) {}";
+        match lines.iter().position(|l| l.trim() == empty) {
+            Some(i) => {
+                lines[i] = "pub fn register(registry: &mut Registry) {".to_owned();
+                lines.insert(i + 1, line);
+                lines.insert(i + 2, "}".to_owned());
+            }
+            None => return Err("no register function in lib.rs".to_owned()),
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn answers_header(year: u32) -> String {
+    format!(
+        "# Known-correct answers for the {} puzzles, checked by `advent verify` and\n\
+         # by the generated tests in advent/tests/verify.rs. Days marked slow are\n\
+         # only run by `cargo test -- --ignored`.\n",
+        year
+    )
+}
+
+// Add an empty table for the day to answers.toml, so the day is checked by
+// the verify tests as soon as its answers are filled in
+fn add_answers_table(answers: &str, day: u32) -> Option<String> {
+    let table = format!("[day{}]", day);
+    if answers.lines().any(|l| l.trim() == table) {
+        None
+    } else {
+        Some(format!("{}\n\n{}\n", answers.trim_end(), table))
+    }
+}
+
+// Generate a new day in the repository at `root`, returning the files which
+// were created or changed. Nothing is written unless the whole day can be
+// added, and an existing module is never overwritten.
+pub fn create(root: &Path, year: u32, day: u32, title: &str) -> Result<Vec<PathBuf>, String> {
+    let krate = root.join(format!("advent{:02}", year % 100));
+    let lib_path = krate.join("src").join("lib.rs");
+    if !lib_path.is_file() {
+        return Err(format!(
+            "{} does not exist; a new year needs its own crate first",
+            lib_path.display()
+        ));
+    }
+
+    let module_path = krate.join("src").join(format!("day{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let lib = read(&lib_path)?;
+    let lib = add_module(&lib, day)?;
+    let lib = add_registration(&lib, year, day, title)?;
+
+    let resources = root.join("resources").join(year.to_string());
+    let input_path = resources.join(format!("day{}", day));
+    let answers_path = resources.join("answers.toml");
+    let answers = if answers_path.is_file() {
+        add_answers_table(&read(&answers_path)?, day)
+    } else {
+        add_answers_table(&answers_header(year), day)
+    };
+
+    write(&module_path, &render(year, day, title))?;
+    write(&lib_path, &lib)?;
+    let mut changed = vec![module_path, lib_path];
+
+    fs::create_dir_all(&resources)
+        .map_err(|e| format!("failed to create {}: {}", resources.display(), e))?;
+    // Keep any input which was already saved for the day
+    if !input_path.exists() {
+        write(&input_path, "")?;
+        changed.push(input_path);
+    }
+    if let Some(answers) = answers {
+        write(&answers_path, &answers)?;
+        changed.push(answers_path);
+    }
+
+    Ok(changed)
+}
+
+#[test]
+fn test_add_module() {
+    let lib = "extern crate advent_core;\n\nmod day1;\nmod day3;\n\nuse advent_core::Registry;\n";
+    assert_eq!(
+        "extern crate advent_core;\n\nmod day1;\nmod day2;\nmod day3;\n\nuse advent_core::Registry;\n",
+        add_module(lib, 2).unwrap()
+    );
+    assert_eq!(
+        "extern crate advent_core;\n\nmod day1;\nmod day3;\nmod day10;\n\nuse advent_core::Registry;\n",
+        add_module(lib, 10).unwrap()
+    );
+    assert!(add_module(lib, 3).is_err());
+
+    let empty = "extern crate advent_core;\n\nuse advent_core::Registry;\n";
+    assert_eq!(
+        "extern crate advent_core;\n\nmod day1;\n\nuse advent_core::Registry;\n",
+        add_module(empty, 1).unwrap()
+    );
+}
+
+#[test]
+fn test_add_registration() {
+    let empty = "pub fn register(_registry: &mut Registry) {}\n";
+    let lib = add_registration(empty, 2017, 2, "Corruption Checksum").unwrap();
+    assert_eq!(
+        "pub fn register(registry: &mut Registry) {\n    \
+         registry.register(2017, 2, \"Corruption Checksum\", day2::Day2);\n}\n",
+        lib
+    );
+
+    let lib = add_registration(&lib, 2017, 1, "Inverse Captcha").unwrap();
+    assert_eq!(
+        "pub fn register(registry: &mut Registry) {\n    \
+         registry.register(2017, 1, \"Inverse Captcha\", day1::Day1);\n    \
+         registry.register(2017, 2, \"Corruption Checksum\", day2::Day2);\n}\n",
+        lib
+    );
+    assert!(add_registration(&lib, 2017, 2, "Again").is_err());
+}
+
+#[test]
+fn test_create() {
+    let root = env::temp_dir().join(format!("advent-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("advent17/src")).unwrap();
+    write(
+        &root.join("advent17/src/lib.rs"),
+        "extern crate advent_core;\n\nuse advent_core::Registry;\n\n\
+         pub fn register(_registry: &mut Registry) {}\n",
+    )
+    .unwrap();
+
+    let created = create(&root, 2017, 3, "Spiral Memory").unwrap();
+    assert_eq!(4, created.len());
+    assert!(read(&root.join("advent17/src/day3.rs"))
+        .unwrap()
+        .contains("--- Day 3: Spiral Memory ---"));
+    assert!(read(&root.join("resources/2017/answers.toml"))
+        .unwrap()
+        .ends_with("\n\n[day3]\n"));
+
+    // Existing work is left alone
+    let lib = read(&root.join("advent17/src/lib.rs")).unwrap();
+    assert!(create(&root, 2017, 3, "Spiral Memory").is_err());
+    assert_eq!(lib, read(&root.join("advent17/src/lib.rs")).unwrap());
+    assert!(create(&root, 2018, 1, "Chronal Calibration").is_err());
+
+    fs::remove_dir_all(&root).unwrap();
+}
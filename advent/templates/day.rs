@@ -0,0 +1,26 @@
+// https://adventofcode.com/{{year}}/day/{{day}}
+/*
+--- Day {{day}}: {{title}} ---
+*/
+use advent_core::{parse_lines, Answer, ParseError, Solution};
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input, |line| Ok(line.to_owned()))
+    }
+
+    fn part1(&self, _input: &Vec<String>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[test]
+fn test_examples() {
+    // Check the examples from the puzzle description here
+    let input = Day{{day}}.parse("").unwrap();
+    assert_eq!(Answer::Unsolved, Day{{day}}.part1(&input));
+}
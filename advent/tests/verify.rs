@@ -0,0 +1,74 @@
+// Checks the solutions against their real inputs and recorded answers. The
+// individual tests are generated by build.rs, one for each day in the
+// resources/<year>/answers.toml files; the slow ones are only run with
+// `cargo test -- --ignored`.
+
+extern crate advent15;
+extern crate advent16;
+extern crate advent17;
+extern crate advent_core;
+
+use std::path::Path;
+
+use advent_core::{Answers, InputLoader, Registry};
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    advent15::register(&mut registry);
+    advent16::register(&mut registry);
+    advent17::register(&mut registry);
+    registry
+}
+
+fn loader() -> InputLoader {
+    InputLoader::with_resources(Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources"))
+}
+
+fn answers(loader: &InputLoader, year: u32) -> Answers {
+    Answers::load(loader.answers_path(year).unwrap()).unwrap()
+}
+
+fn verify(year: u32, day: u32) {
+    let registry = registry();
+    let loader = loader();
+
+    let solution = match registry.get(year, day) {
+        Some(s) => s,
+        None => panic!("{} day {} has recorded answers but no solution", year, day),
+    };
+    let expected = answers(&loader, year).get(day).cloned().unwrap();
+    let input = loader
+        .locate_resource(year, day)
+        .and_then(|source| source.read())
+        .unwrap();
+
+    for (part, verdict) in advent_core::verify(solution, &input, &expected).unwrap() {
+        assert!(
+            !verdict.is_failure(),
+            "{} day {} part {}: {}",
+            year,
+            day,
+            part,
+            verdict
+        );
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/verify_tests.rs"));
+
+// Every registered day needs an entry in its answers file, even an empty one,
+// so that it gets a test above
+#[test]
+fn test_all_days_recorded() {
+    let loader = loader();
+
+    for day in registry().days() {
+        assert!(
+            answers(&loader, day.year).get(day.day).is_some(),
+            "{} day {} is missing from resources/{}/answers.toml",
+            day.year,
+            day.day,
+            day.year
+        );
+    }
+}
@@ -1,6 +1,6 @@
 // https://adventofcode.com/2015/day/1
 
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 
 fn compute_floor(s: &String) -> i32 {
     let mut floor = 0;
@@ -15,13 +15,45 @@ fn compute_floor(s: &String) -> i32 {
     floor
 }
 
-fn print_answer(s: &String) {
-    println!("Santa is in {} floor", compute_floor(s));
+// Position (starting at 1) of the first character which takes Santa into the
+// basement
+fn first_basement_position(s: &str) -> Option<usize> {
+    let mut floor = 0;
+    for (i, c) in s.chars().enumerate() {
+        floor += if c == '(' { 1 } else { -1 };
+        if floor < 0 {
+            return Some(i + 1);
+        }
+    }
+
+    None
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        print_answer(&line.unwrap());
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        let line = input.trim();
+        match line.chars().position(|c| c != '(' && c != ')') {
+            Some(i) => Err(ParseError::new(&line[i..i + 1], "\"(\" or \")\"").at(1, i + 1)),
+            None => Ok(line.to_owned()),
+        }
     }
+
+    fn part1(&self, input: &String) -> Answer {
+        compute_floor(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        first_basement_position(input).into()
+    }
+}
+
+#[test]
+fn test_first_basement_position() {
+    assert_eq!(Some(1), first_basement_position(")"));
+    assert_eq!(Some(5), first_basement_position("()())"));
+    assert_eq!(None, first_basement_position("(()"));
 }
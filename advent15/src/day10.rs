@@ -25,7 +25,7 @@ is the length of the result?
 
 Your puzzle input is 1113122113
 */
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 
 type LookAndSaySeries = Vec<i32>;
 
@@ -78,27 +78,26 @@ fn look_and_say_next(series: &LookAndSaySeries, steps: i32) -> LookAndSaySeries
     current
 }
 
-fn parse_series(s: &String) -> Option<LookAndSaySeries> {
+fn parse_series(s: &String) -> Result<LookAndSaySeries, ParseError> {
     let mut series: LookAndSaySeries = vec![];
-    for c in s.as_bytes() {
-        if *c >= '0' as u8 && *c <= '9' as u8 {
-            series.push(*c as i32 - '0' as i32);
-        } else {
-            return None;
+    for (i, c) in s.chars().enumerate() {
+        match c.to_digit(10) {
+            Some(d) => series.push(d as i32),
+            None => return Err(ParseError::new(c.to_string(), "a digit").at(1, i + 1)),
         }
     }
-    Some(series.clone())
+    Ok(series)
 }
 
 #[allow(dead_code)]
 fn test_look_and_say() {
     match parse_series(&"1".to_owned()) {
-        Some(s) => {
+        Ok(s) => {
             let srs = look_and_say_next(&s, 40);
             println!("Length: {}", srs.len());
         }
-        None => {
-            println!("Failed to parse series");
+        Err(e) => {
+            println!("Failed to parse series: {}", e);
         }
     }
 
@@ -114,20 +113,21 @@ fn test_look_and_say() {
     }
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+pub struct Day10;
 
-        match parse_series(&sline) {
-            Some(s) => {
-                let ls = look_and_say_next(&s, 40);
-                println!("Length: {}", ls.len());
-            }
-            None => {
-                println!("Failed to parse series: {}", sline);
-            }
-        }
+impl Solution for Day10 {
+    type Input = LookAndSaySeries;
+
+    fn parse(&self, input: &str) -> Result<LookAndSaySeries, ParseError> {
+        parse_series(&input.trim().to_owned())
+    }
+
+    fn part1(&self, input: &LookAndSaySeries) -> Answer {
+        look_and_say_next(input, 40).len().into()
+    }
+
+    fn part2(&self, input: &LookAndSaySeries) -> Answer {
+        look_and_say_next(input, 50).len().into()
     }
 }
 
@@ -37,10 +37,10 @@ Given Santa's current password (your puzzle input), what should his next passwor
 
 Your puzzle input is hepxcrrq.
 */
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 
 #[derive(Debug, PartialEq)]
-struct Password {
+pub struct Password {
     pass: Vec<u8>,
 }
 
@@ -58,18 +58,18 @@ impl Password {
         }
     }
 
-    fn from_str(s: &String) -> Option<Password> {
+    fn from_str(s: &String) -> Result<Password, ParseError> {
         let mut data: Vec<u8> = vec![];
 
-        for c in s.as_bytes() {
-            if 'a' as u8 <= *c || *c <= 'z' as u8 {
-                data.push(*c);
+        for (i, c) in s.chars().enumerate() {
+            if c.is_ascii_lowercase() {
+                data.push(c as u8);
             } else {
-                return None;
+                return Err(ParseError::new(c.to_string(), "a lowercase letter").at(1, i + 1));
             }
         }
 
-        Some(Password { pass: data })
+        Ok(Password { pass: data })
     }
 
     fn has_increasing_seq(&self) -> bool {
@@ -204,17 +204,25 @@ fn test_pass() {
     }
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+pub struct Day11;
 
-        match Password::from_str(&sline) {
-            Some(p) => {
-                println!("{} -> {}", sline, next_valid(&p).unwrap().to_string());
-            },
-            None => { println!("Invalid password: {}", sline); }
-        }
+impl Solution for Day11 {
+    type Input = Password;
+
+    fn parse(&self, input: &str) -> Result<Password, ParseError> {
+        Password::from_str(&input.trim().to_owned())
+    }
+
+    fn part1(&self, input: &Password) -> Answer {
+        next_valid(input).map(|p| p.to_string()).into()
+    }
+
+    // The password expires again, so find the one after the next
+    fn part2(&self, input: &Password) -> Answer {
+        next_valid(input)
+            .and_then(|p| next_valid(&p))
+            .map(|p| p.to_string())
+            .into()
     }
 }
 
@@ -229,7 +237,7 @@ fn test_valid_passwords() {
 
 #[test]
 fn test_example() {
-    let expected = Password::from_str(&"ghjaabcc".to_owned());
+    let expected = Password::from_str(&"ghjaabcc".to_owned()).ok();
     let next = next_valid(&Password::from_str(&"ghijklmn".to_owned()).unwrap());
     assert_eq!(expected, next);
 }
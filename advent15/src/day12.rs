@@ -24,7 +24,7 @@ What is the sum of all numbers in the document?
 extern crate serde_json;
 
 use self::serde_json::Value;
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 
 fn sum_numbers(v: Value) -> i64 {
     match v {
@@ -59,7 +59,23 @@ fn sum_numbers(v: Value) -> i64 {
     }
 }
 
+// Sum the numbers like sum_numbers, but leave out any object (and everything
+// inside it) which has a property with the given string value
+fn sum_numbers_except(v: &Value, ignore: &str) -> i64 {
+    match *v {
+        Value::Array(ref vals) => vals.iter().map(|v| sum_numbers_except(v, ignore)).sum(),
+        Value::Object(ref map) => {
+            if map.values().any(|val| val.as_str() == Some(ignore)) {
+                0
+            } else {
+                map.values().map(|v| sum_numbers_except(v, ignore)).sum()
+            }
+        }
+        _ => sum_numbers(v.clone()),
+    }
+}
 
+#[cfg(test)]
 fn calculate_sum(data: &String) -> Option<i64> {
     match serde_json::from_str(data) {
         Ok(val) => Some(sum_numbers(val)),
@@ -67,15 +83,32 @@ fn calculate_sum(data: &String) -> Option<i64> {
     }
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Value;
+
+    fn parse(&self, input: &str) -> Result<Value, ParseError> {
+        serde_json::from_str(input).map_err(|e| {
+            // Point at the text where the JSON stopped making sense
+            let rest: String = input
+                .lines()
+                .nth(e.line().max(1) - 1)
+                .unwrap_or("")
+                .chars()
+                .skip(e.column().max(1) - 1)
+                .take(20)
+                .collect();
+            ParseError::new(rest, "valid JSON").at(e.line(), e.column())
+        })
+    }
+
+    fn part1(&self, input: &Value) -> Answer {
+        sum_numbers(input.clone()).into()
+    }
 
-        match calculate_sum(&sline) {
-            Some(num) => println!("Sum is: {}", num),
-            None => println!("Error - failed to parse json for: {}", sline),
-        }
+    fn part2(&self, input: &Value) -> Answer {
+        sum_numbers_except(input, "red").into()
     }
 }
 
@@ -96,3 +129,13 @@ fn test_calculate() {
                   }"#;
     assert_eq!(calculate_sum(&data2.to_owned()), Some(43));
 }
+
+#[test]
+fn test_ignore_red() {
+    let sum = |data: &str| sum_numbers_except(&serde_json::from_str(data).unwrap(), "red");
+
+    assert_eq!(6, sum(r#"[1,2,3]"#));
+    assert_eq!(4, sum(r#"[1,{"c":"red","b":2},3]"#));
+    assert_eq!(0, sum(r#"{"d":"red","e":[1,2,3,4],"f":5}"#));
+    assert_eq!(6, sum(r#"[1,"red",5]"#));
+}
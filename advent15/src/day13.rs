@@ -50,13 +50,13 @@ What is the total change in happiness for the optimal seating arrangement of the
 actual guest list?
 */
 
-extern crate permutohedron;
-extern crate regex;
-
-use std::io::{self, BufRead};
-use std::collections::HashMap;
-use self::regex::Regex;
-use self::permutohedron::heap_recursive;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+#[cfg(test)]
+use advent_core::exported;
+use advent_core::grammar::{after, keyword, name, number, one_of, parse_line};
+use advent_core::graph::{Edges, Goal, Graph, Shape, MAX_NODES};
+use advent_core::{parse_lines, Answer, ExportError, ParseError, Setting, Solution};
 
 type Pairing = (String, String);
 type HappinessInfo = (Pairing, i32);
@@ -91,152 +91,238 @@ fn get_happiness(map: &HappinessMap, p: &Pairing) -> i32 {
     }
 }
 
-fn minimum_total(map: &HappinessMap) -> Option<i32> {
-    let mut people: Vec<String> = vec![];
-    let mut phash: HashMap<String, String> = HashMap::new();
-
-    for (&(ref p1, ref p2), _) in map {
-        phash.insert(p1.to_owned(), p1.to_owned());
-        phash.insert(p2.to_owned(), p2.to_owned());
+// Everyone at the table, with an edge between each two people for how much
+// happier sitting together makes the pair of them
+fn seating(map: &HappinessMap) -> Graph<i32> {
+    let people: BTreeSet<&String> = map.keys().flat_map(|pair| vec![&pair.0, &pair.1]).collect();
+    let people: Vec<&String> = people.into_iter().collect();
+    let mut graph = Graph::new(Edges::Undirected);
+
+    for (i, &p1) in people.iter().enumerate() {
+        for &p2 in &people[i + 1..] {
+            let together = get_happiness(map, &(p1.to_owned(), p2.to_owned()))
+                + get_happiness(map, &(p2.to_owned(), p1.to_owned()));
+            graph.add_edge(p1, p2, together);
+        }
     }
+    graph
+}
 
-    for p in phash.keys() {
-        people.push(p.to_owned());
-    }
+// Two people sitting next to each other, and how much happier each is for it
+#[derive(Debug, Clone, PartialEq)]
+struct Neighbours {
+    left: String,
+    right: String,
+    left_gain: i32,
+    right_gain: i32,
+}
 
-    let mut total: Option<i32> = None;
+// Who sits in each seat round the table, the last one being next to the
+// first, and what each pair of neighbours makes of sitting together
+#[derive(Debug, Clone, PartialEq)]
+struct Seating {
+    people: Vec<String>,
+    neighbours: Vec<Neighbours>,
+    total: i32,
+}
 
-    heap_recursive(&mut people, |people_perm| {
-        let new_total = calculate_happiness_total(map, &people_perm.to_vec());
+impl fmt::Display for Seating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for pair in &self.neighbours {
+            writeln!(
+                f,
+                "{} ({:+}) next to {} ({:+})",
+                pair.left, pair.left_gain, pair.right, pair.right_gain
+            )?;
+        }
+        write!(f, "Total change in happiness: {}", self.total)
+    }
+}
 
-        match total {
-            Some(cur) => {
-                if cur < new_total {
-                    total = Some(new_total);
-                }
-            }
-            None => {
-                total = Some(new_total);
+// The happiest way of seating everyone, with some people in given seats.
+// Seats are numbered round the table, and without any fixed seats the first
+// person in order of name takes seat 0, so that arrangements which are only
+// turned round the table from one another are only tried once.
+fn arrange(map: &HappinessMap, seats: &[(String, usize)]) -> Option<Seating> {
+    let table = seating(map);
+    let n = table.len();
+
+    let mut placed = vec![];
+    for &(ref person, seat) in seats {
+        placed.push((seat, table.id(person)?));
+    }
+    let walk = table.best_placed(Shape::Tour, Goal::Max, &placed)?;
+
+    let people: Vec<String> = walk
+        .nodes
+        .iter()
+        .map(|&id| table.name(id).to_owned())
+        .collect();
+    let neighbours = (0..n)
+        .map(|seat| {
+            let (left, right) = (&people[seat], &people[(seat + 1) % n]);
+            Neighbours {
+                left: left.to_owned(),
+                right: right.to_owned(),
+                left_gain: get_happiness(map, &(left.to_owned(), right.to_owned())),
+                right_gain: get_happiness(map, &(right.to_owned(), left.to_owned())),
             }
-        }
-    });
+        })
+        .collect();
+
+    Some(Seating {
+        people,
+        neighbours,
+        total: walk.weight,
+    })
+}
 
-    total
+fn solve(map: &HappinessMap, seats: &[(String, usize)]) -> Answer {
+    let seating = arrange(map, seats);
+    if let Some(ref seating) = seating {
+        debug!("Seating {:?}\n{}", seating.people, seating);
+    }
+    seating.map(|seating| seating.total).into()
 }
 
-fn calculate_happiness_total(map: &HappinessMap, people: &Vec<String>) -> i32 {
-    let mut total: i32 = 0;
-    let mut left: String;
-    let mut right: String;
+// Add a guest to the list, neutral to everyone and everyone to them, unless
+// they are on it already
+fn with_guest(map: &HappinessMap, guest: &str) -> Result<HappinessMap, ParseError> {
+    if map.keys().any(|pair| pair.0 == guest) {
+        return Err(ParseError::new(guest, "a guest who isn't on the list yet"));
+    }
+    let mut with_guest = map.clone();
 
-    if people.len() <= 2 {
-        return total;
+    for (p1, _) in map.keys() {
+        with_guest.insert((guest.to_owned(), p1.to_owned()), 0);
+        with_guest.insert((p1.to_owned(), guest.to_owned()), 0);
     }
 
-    for i in 0..people.len() {
-        let person = &people[i];
+    Ok(with_guest)
+}
 
-        if i == 0 {
-            left = people[people.len() - 1].to_owned();
-            right = people[1].to_owned();
-        } else if i + 1 == people.len() {
-            left = people[i - 1].to_owned();
-            right = people[0].to_owned();
-        } else {
-            left = people[i - 1].to_owned();
-            right = people[i + 1].to_owned();
-        }
+fn parse_happiness_desc(s: &str) -> Result<HappinessInfo, ParseError> {
+    let desc = (
+        name(),
+        after("would", one_of(&["gain", "lose"])),
+        number::<i32>(),
+        after("happiness units by sitting next to", name()),
+        keyword("."),
+    );
+    let (person1, change, num, person2, ()) = parse_line(s, desc)?;
 
-        total += get_happiness(map, &(person.to_owned(), left));
-        total += get_happiness(map, &(person.to_owned(), right));
-    }
+    // Negative value for happiness
+    let num = if change == "lose" { -num } else { num };
+    Ok(((person1, person2), num))
+}
 
-    total
+// The guest list with how everyone feels about everyone else, and anyone who
+// has to sit in a given seat
+pub struct Table {
+    happiness: HappinessMap,
+    seats: Vec<(String, usize)>,
 }
 
-fn parse_happiness_desc(s: &String) -> Option<HappinessInfo> {
-    lazy_static! {
-        static ref R_GAIN: Regex =
-            Regex::new(r"(\w+) would gain (\d+) happiness units by sitting next to (\w+).").unwrap();
+pub struct Day13;
 
-        static ref R_LOSE: Regex =
-            Regex::new(r"(\w+) would lose (\d+) happiness units by sitting next to (\w+).").unwrap();
+// Extra guests are added with the setting "guest=Name", and a guest is put
+// in a seat with "seat=Name:2". The seating part one finds can be written out
+// with "seating=FILE", or "seating=-" for standard output.
+impl Solution for Day13 {
+    type Input = Table;
+
+    fn parse(&self, input: &str) -> Result<Table, ParseError> {
+        self.parse_with(input, &[])
     }
 
-    if R_GAIN.is_match(s) {
-        for cap in R_GAIN.captures_iter(s) {
-            let person1: String = cap[1].to_owned();
-            let value: String = cap[2].to_owned();
-            let person2: String = cap[3].to_owned();
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Table, ParseError> {
+        let mut happiness = HappinessMap::new();
+        for desc in parse_lines(input, parse_happiness_desc)? {
+            add_to_happiness_map(&mut happiness, &desc);
+        }
 
-            match value.parse::<i32>() {
-                Ok(num) => {
-                    let info: HappinessInfo = ((person1, person2), num);
-                    return Some(info);
+        let mut seats: Vec<(String, usize)> = vec![];
+        for setting in settings {
+            match setting.name.as_str() {
+                "guest" => happiness = with_guest(&happiness, &setting.value)?,
+                "seat" => seats.push(parse_line(&setting.value, (name(), after(":", number())))?),
+                "seating" => {}
+                _ => {
+                    return Err(ParseError::new(
+                        setting.name.as_str(),
+                        "\"guest\", \"seat\" or \"seating\"",
+                    ))
                 }
-                Err(_) => return None,
             }
         }
-    }
 
-    if R_LOSE.is_match(s) {
-        for cap in R_LOSE.captures_iter(s) {
-            let person1: String = cap[1].to_owned();
-            let value: String = cap[2].to_owned();
-            let person2: String = cap[3].to_owned();
-
-            match value.parse::<i32>() {
-                Ok(num) => {
-                    // Negative value for happiness
-                    let info: HappinessInfo = ((person1, person2), -num);
-                    return Some(info);
-                }
-                Err(_) => return None,
+        let people: BTreeSet<&String> = happiness.keys().map(|pair| &pair.0).collect();
+        // Too many people to try every seating. Part two seats one more, which
+        // leaves it unsolved when the table is already full.
+        if people.len() > MAX_NODES {
+            return Err(ParseError::new(
+                format!("{} people", people.len()),
+                format!("at most {} people round the table", MAX_NODES),
+            ));
+        }
+        for (i, &(ref person, seat)) in seats.iter().enumerate() {
+            let taken = seats[..i]
+                .iter()
+                .any(|&(ref p, s)| p == person || s == seat);
+            if !people.contains(person) {
+                return Err(ParseError::new(
+                    person.as_str(),
+                    "someone on the guest list",
+                ));
+            } else if seat >= people.len() || taken {
+                return Err(ParseError::new(
+                    format!("{}:{}", person, seat),
+                    format!("a seat of its own from 0 to {}", people.len() - 1),
+                ));
             }
         }
-    }
-
-    None
-}
-
-pub fn problem() {
-    let mut map = HappinessMap::new();
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+        Ok(Table { happiness, seats })
+    }
 
-        match parse_happiness_desc(&sline) {
-            Some(desc) => {
-                add_to_happiness_map(&mut map, &desc);
-            }
-            None => {
-                println!("Failed to parse: {}", sline);
+    fn export(&self, input: &Table, settings: &[Setting]) -> Result<(), ExportError> {
+        for setting in settings.iter().filter(|setting| setting.name == "seating") {
+            if let Some(seating) = arrange(&input.happiness, &input.seats) {
+                setting.write(format!("{}\n", seating).as_bytes())?;
             }
         }
+        Ok(())
+    }
+
+    fn part1(&self, input: &Table) -> Answer {
+        solve(&input.happiness, &input.seats)
     }
 
-    println!("Optimal total: {}", minimum_total(&map).unwrap());
+    // Unsolved if you were already added as a guest
+    fn part2(&self, input: &Table) -> Answer {
+        match with_guest(&input.happiness, "Yourself") {
+            Ok(happiness) => solve(&happiness, &input.seats),
+            Err(_) => Answer::Unsolved,
+        }
+    }
 }
 
 #[test]
 fn test_parsing_happiness_desc() {
     {
         // happiness gain parsing
-        let info = parse_happiness_desc(
-            &"Alice would gain 54 happiness units by sitting next to Bob.".to_owned(),
-        );
+        let info =
+            parse_happiness_desc("Alice would gain 54 happiness units by sitting next to Bob.");
 
-        assert!(info.is_some());
+        assert_eq!(Ok((("Alice".to_owned(), "Bob".to_owned()), 54)), info);
     }
 
     {
         // happiness lose parsing
-        let info = parse_happiness_desc(
-            &"Alice would lose 79 happiness units by sitting next to Carol.".to_owned(),
-        );
+        let info =
+            parse_happiness_desc("Alice would lose 79 happiness units by sitting next to Carol.");
 
-        assert!(info.is_some());
+        assert_eq!(Ok((("Alice".to_owned(), "Carol".to_owned()), -79)), info);
     }
 }
 
@@ -280,16 +366,91 @@ fn test_example_arrangement() {
         add_to_happiness_map(&mut map, &david_carol);
     }
 
+    assert_eq!(Some(330), arrange(&map, &[]).map(|seating| seating.total));
+    let table = seating(&map);
+    let people: Vec<usize> = ["David", "Alice", "Bob", "Carol"]
+        .iter()
+        .map(|person| table.id(person).unwrap())
+        .collect();
+    assert_eq!(Some(330), table.total(&people, Shape::Tour));
+}
+
+#[test]
+fn test_seating() {
+    let example = "Alice would gain 54 happiness units by sitting next to Bob.
+        Alice would lose 79 happiness units by sitting next to Carol.
+        Alice would lose 2 happiness units by sitting next to David.
+        Bob would gain 83 happiness units by sitting next to Alice.
+        Bob would lose 7 happiness units by sitting next to Carol.
+        Bob would lose 63 happiness units by sitting next to David.
+        Carol would lose 62 happiness units by sitting next to Alice.
+        Carol would gain 60 happiness units by sitting next to Bob.
+        Carol would gain 55 happiness units by sitting next to David.
+        David would gain 46 happiness units by sitting next to Alice.
+        David would lose 7 happiness units by sitting next to Bob.
+        David would gain 41 happiness units by sitting next to Carol.";
+
+    let table = Day13.parse(example).unwrap();
+    let seating = arrange(&table.happiness, &table.seats).unwrap();
+    assert_eq!(330, seating.total);
+    assert_eq!("Alice", seating.people[0]);
+    let gains = seating
+        .neighbours
+        .iter()
+        .map(|pair| pair.left_gain + pair.right_gain);
+    assert_eq!(330, gains.sum::<i32>());
+
+    let settings = [
+        Setting::new("guest", "Zoe"),
+        Setting::new("seat", "Carol:3"),
+        Setting::new("seat", "Alice:4"),
+    ];
+    let table = Day13.parse_with(example, &settings).unwrap();
+    let seating = arrange(&table.happiness, &table.seats).unwrap();
+    assert_eq!(92, seating.total);
+    assert_eq!(
+        vec!["Bob", "Zoe", "David", "Carol", "Alice"],
+        seating.people
+    );
+    assert_eq!(
+        Neighbours {
+            left: "Carol".to_owned(),
+            right: "Alice".to_owned(),
+            left_gain: -62,
+            right_gain: -79,
+        },
+        seating.neighbours[3]
+    );
+
+    let written = exported("seating", |settings| Day13.export(&table, settings));
+    assert_eq!(
+        format!("{}\n", seating),
+        String::from_utf8(written).unwrap()
+    );
+
+    assert_eq!(
+        Err(ParseError::new(
+            "colour",
+            "\"guest\", \"seat\" or \"seating\""
+        )),
+        Day13
+            .parse_with(example, &[Setting::new("colour", "red")])
+            .map(|_| ())
+    );
+    assert!(Day13
+        .parse_with(example, &[Setting::new("seat", "Zoe:0")])
+        .is_err());
+    assert!(Day13
+        .parse_with(example, &[Setting::new("seat", "Bob:4")])
+        .is_err());
     assert_eq!(
-        330,
-        calculate_happiness_total(
-            &map,
-            &vec![
-                "David".to_owned(),
-                "Alice".to_owned(),
-                "Bob".to_owned(),
-                "Carol".to_owned(),
-            ]
-        )
+        Err(ParseError::new("Bob", "a guest who isn't on the list yet")),
+        Day13
+            .parse_with(example, &[Setting::new("guest", "Bob")])
+            .map(|_| ())
     );
+    let table = Day13
+        .parse_with(example, &[Setting::new("guest", "Yourself")])
+        .unwrap();
+    assert_eq!(Answer::Unsolved, Day13.part2(&table));
 }
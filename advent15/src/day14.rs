@@ -29,124 +29,222 @@ Given the descriptions of each reindeer (in your puzzle input), after exactly
 2503 seconds, what distance has the winning reindeer travelled?
 */
 
-extern crate regex;
+#[cfg(test)]
+use advent_core::exported;
+use advent_core::grammar::{after, keyword, name, number, parse_line, Parser};
+use advent_core::{parse_lines, Answer, ExportError, ParseError, Setting, Solution};
 
-use std::io::{self, BufRead};
-use self::regex::Regex;
+// How many seconds the race lasts
+const RACE: i32 = 2503;
 
 #[derive(Debug, Clone)]
-struct Reindeer {
+pub struct Reindeer {
     name: String,
     speed: i32,
     run_duration: i32,
     rest_duration: i32,
 }
 
+// A number no less than the minimum, like the speed of a reindeer, or how long
+// it flies for, which has to be more than none for it to ever get anywhere
+fn at_least(min: i32, expected: &'static str) -> impl Parser<i32> {
+    move |line: &str, pos: usize| {
+        let (n, end) = number().parse_at(line, pos)?;
+        if n >= min {
+            return Ok((n, end));
+        }
+
+        let start = line.len() - line[pos..].trim_start().len();
+        let column = line[..start].chars().count() + 1;
+        Err(ParseError::new(&line[start..end], expected).at(0, column))
+    }
+}
+
 impl Reindeer {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Whether the reindeer is flying during the given second, counting from 0
+    fn flying(&self, second: i32) -> bool {
+        second % (self.run_duration + self.rest_duration) < self.run_duration
+    }
+
     fn distance_travelled(&self, period: i32) -> i32 {
         let mut p = period;
         let mut distance = 0;
 
-        loop {
-            if p < 0 || p < self.run_duration {
-                break;
-            }
-
-            // p >= self.run_duration so we can run
-            distance += self.run_duration * self.speed;
+        while p > 0 {
+            // Fly for as much of the run as there is time left for
+            distance += p.min(self.run_duration) * self.speed;
             p -= self.run_duration + self.rest_duration;
         }
         distance
     }
 
-    fn from_description(s: &String) -> Option<Reindeer> {
-        lazy_static! {
-            static ref R_DESC: Regex =
-                Regex::new(r"(\w+) can fly (\d+) km/s for (\d+) seconds, but then must rest for (\d+) seconds.").unwrap();
+    pub fn from_description(s: &str) -> Result<Reindeer, ParseError> {
+        let desc = (
+            name(),
+            after("can fly", at_least(0, "a speed of 0 or more")),
+            after("km/s for", at_least(1, "a positive number of seconds")),
+            after(
+                "seconds, but then must rest for",
+                at_least(0, "a number of seconds of 0 or more"),
+            ),
+            keyword("seconds."),
+        );
+        let (name, speed, run_duration, rest_duration, ()) = parse_line(s, desc)?;
+
+        Ok(Reindeer {
+            name,
+            speed,
+            run_duration,
+            rest_duration,
+        })
+    }
+}
+
+// The reindeer furthest ahead after the period, all of them if there's a tie,
+// and how far they got
+fn find_fastest(deers: &[Reindeer], period: i32) -> (Vec<&Reindeer>, i32) {
+    let lead = deers
+        .iter()
+        .map(|d| d.distance_travelled(period))
+        .max()
+        .unwrap_or(0);
+    let fastest = deers
+        .iter()
+        .filter(|d| d.distance_travelled(period) == lead)
+        .collect();
+
+    (fastest, lead)
+}
+
+/// How a race stands at the end of a second: how far each reindeer has gone
+/// and how many points each has, in the order the reindeer were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub second: i32,
+    pub distances: Vec<i32>,
+    pub points: Vec<i32>,
+}
+
+impl Standing {
+    /// Which reindeer are in the lead, all of them if there's a tie.
+    pub fn leaders(&self) -> Vec<usize> {
+        let lead = self.distances.iter().cloned().max();
+        (0..self.distances.len())
+            .filter(|&i| Some(self.distances[i]) == lead)
+            .collect()
+    }
+}
+
+/// A race between the reindeer, run a second at a time, giving how it stands
+/// after each second. Every reindeer in the lead at the end of a second gets
+/// a point. The race goes on for ever, so take as many seconds as it lasts.
+pub struct Race<'a> {
+    deers: &'a [Reindeer],
+    standing: Standing,
+}
+
+impl<'a> Race<'a> {
+    pub fn new(deers: &'a [Reindeer]) -> Race<'a> {
+        Race {
+            deers,
+            standing: Standing {
+                second: 0,
+                distances: vec![0; deers.len()],
+                points: vec![0; deers.len()],
+            },
         }
+    }
+}
 
-        if R_DESC.is_match(s) {
-            for cap in R_DESC.captures_iter(s) {
-                let name = cap[1].to_owned();
-                let str_speed = cap[2].to_owned();
-                let str_run_duration = cap[3].to_owned();
-                let str_rest_duration = cap[4].to_owned();
-
-                match str_speed.parse::<i32>() {
-                    Ok(speed) => match str_run_duration.parse::<i32>() {
-                        Ok(run_duration) => match str_rest_duration.parse::<i32>() {
-                            Ok(rest_duration) => {
-                                let deer = Reindeer {
-                                    name: name,
-                                    speed: speed,
-                                    run_duration: run_duration,
-                                    rest_duration: rest_duration,
-                                };
-                                return Some(deer);
-                            }
-                            Err(_) => {
-                                return None;
-                            }
-                        },
-                        Err(_) => {
-                            return None;
-                        }
-                    },
-                    Err(_) => {
-                        return None;
-                    }
-                }
+impl<'a> Iterator for Race<'a> {
+    type Item = Standing;
+
+    fn next(&mut self) -> Option<Standing> {
+        let standing = &mut self.standing;
+        for (d, distance) in self.deers.iter().zip(standing.distances.iter_mut()) {
+            if d.flying(standing.second) {
+                *distance += d.speed;
             }
         }
+        standing.second += 1;
 
-        None
+        for leader in standing.leaders() {
+            standing.points[leader] += 1;
+        }
+        Some(standing.clone())
     }
 }
 
-fn find_fastest(deers: &Vec<Reindeer>, period: i32) -> Option<(Reindeer, i32)> {
-    let mut fastest: Option<(Reindeer, i32)> = None;
+// The points of each reindeer after the period, in order
+fn award_points(deers: &[Reindeer], period: i32) -> Vec<i32> {
+    match Race::new(deers).take(period.max(0) as usize).last() {
+        Some(standing) => standing.points,
+        None => vec![0; deers.len()],
+    }
+}
 
-    for d in deers.iter() {
-        let cur = d.distance_travelled(period);
+/// The race second by second as CSV, for plotting: a header naming the
+/// columns, then a row for each second with the distance and the points of
+/// each reindeer in turn.
+pub fn timeline_csv(deers: &[Reindeer], seconds: i32) -> String {
+    let mut csv = String::from("second");
+    for d in deers {
+        csv += &format!(",{} distance,{} points", d.name, d.name);
+    }
+    csv.push('\n');
 
-        match fastest {
-            Some((_, dist)) => {
-                // if current deer is faster, then set update the fastest
-                if cur > dist {
-                    fastest = Some((d.clone(), cur));
-                }
-            }
-            None => {
-                fastest = Some((d.clone(), cur));
-            }
+    for standing in Race::new(deers).take(seconds.max(0) as usize) {
+        csv += &standing.second.to_string();
+        for (distance, points) in standing.distances.iter().zip(&standing.points) {
+            csv += &format!(",{},{}", distance, points);
         }
+        csv.push('\n');
     }
-
-    fastest
+    csv
 }
 
-pub fn problem() {
-    let mut deers: Vec<Reindeer> = vec![];
+pub struct Day14;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day14 {
+    type Input = Vec<Reindeer>;
 
-        match Reindeer::from_description(&sline) {
-            Some(deer) => {
-                deers.push(deer);
-            }
-            None => {
-                println!("Error: failed to parse Reindeer description: {}", sline);
+    fn parse(&self, input: &str) -> Result<Vec<Reindeer>, ParseError> {
+        parse_lines(input, Reindeer::from_description)
+    }
+
+    // The only setting is the CSV `export` writes
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Vec<Reindeer>, ParseError> {
+        match settings.iter().find(|setting| setting.name != "csv") {
+            Some(setting) => Err(ParseError::new(setting.name.as_str(), "\"csv\"")),
+            None => self.parse(input),
+        }
+    }
+
+    // The whole race can be written out as CSV with `csv`, to a file or to
+    // standard output for "-"
+    fn export(&self, input: &Vec<Reindeer>, settings: &[Setting]) -> Result<(), ExportError> {
+        for setting in settings {
+            if setting.name == "csv" {
+                setting.write(timeline_csv(input, RACE).as_bytes())?;
             }
         }
+        Ok(())
     }
 
-    println!("Deers count: {}", deers.len());
+    fn part1(&self, input: &Vec<Reindeer>) -> Answer {
+        let (fastest, distance) = find_fastest(input, RACE);
+        let names: Vec<&str> = fastest.iter().map(|d| d.name()).collect();
+        debug!("{} travelled {} km", names.join(" and "), distance);
+
+        fastest.first().map(|_| distance).into()
+    }
 
-    match find_fastest(&deers, 2503) {
-        Some(info) => println!("Fastest: {:?}", info),
-        None => println!("Error: couldn't compute fastest Reindeer"),
+    fn part2(&self, input: &Vec<Reindeer>) -> Answer {
+        award_points(input, RACE).into_iter().max().into()
     }
 }
 
@@ -155,8 +253,8 @@ fn test_parse_reindeer() {
     {
         let desc = "Dancer can fly 27 km/s for 5 seconds, but then must rest for 132 seconds.";
 
-        let deer_op = Reindeer::from_description(&desc.to_owned());
-        assert!(deer_op.is_some());
+        let deer_op = Reindeer::from_description(desc);
+        assert!(deer_op.is_ok());
 
         let deer = deer_op.unwrap();
         assert_eq!(deer.name, "Dancer");
@@ -167,8 +265,8 @@ fn test_parse_reindeer() {
     {
         let desc = "Cupid can fly 22 km/s for 2 seconds, but then must rest for 41 seconds.";
 
-        let deer_op = Reindeer::from_description(&desc.to_owned());
-        assert!(deer_op.is_some());
+        let deer_op = Reindeer::from_description(desc);
+        assert!(deer_op.is_ok());
 
         let deer = deer_op.unwrap();
         assert_eq!(deer.name, "Cupid");
@@ -176,6 +274,32 @@ fn test_parse_reindeer() {
         assert_eq!(deer.run_duration, 2);
         assert_eq!(deer.rest_duration, 41);
     }
+
+    // A reindeer which never flies can't be raced, though one which never
+    // rests can
+    assert_eq!(
+        Err(ParseError::new("0", "a positive number of seconds").at(0, 27)),
+        Reindeer::from_description(
+            "Vixen can fly 10 km/s for 0 seconds, but then must rest for 0 seconds."
+        )
+        .map(|_| ())
+    );
+    let vixen = Reindeer::from_description(
+        "Vixen can fly 10 km/s for 3 seconds, but then must rest for 0 seconds.",
+    )
+    .unwrap();
+    assert_eq!(100, vixen.distance_travelled(10));
+    assert!(Reindeer::from_description(
+        "Vixen can fly 10 km/s for 3 seconds, but then must rest for -2 seconds."
+    )
+    .is_err());
+    assert_eq!(
+        Err(ParseError::new("-10", "a speed of 0 or more").at(0, 15)),
+        Reindeer::from_description(
+            "Vixen can fly -10 km/s for 3 seconds, but then must rest for 2 seconds."
+        )
+        .map(|_| ())
+    );
 }
 
 #[test]
@@ -197,8 +321,76 @@ fn test_fastest_computation() {
     assert_eq!(comet.distance_travelled(1000), 1120);
     assert_eq!(dancer.distance_travelled(1000), 1056);
 
-    let fastest = find_fastest(&vec![comet, dancer], 1000);
-    assert!(fastest.is_some());
-    let (d, _) = fastest.unwrap();
-    assert_eq!(d.name, "Comet"); // Comet should be the fastest one
+    assert_eq!(comet.distance_travelled(5), 70);
+    assert_eq!(
+        vec![312, 689],
+        award_points(&[comet.clone(), dancer.clone()], 1000)
+    );
+
+    let deers = vec![comet, dancer];
+    let (fastest, distance) = find_fastest(&deers, 1000);
+    assert_eq!(1, fastest.len());
+    assert_eq!(fastest[0].name, "Comet"); // Comet should be the fastest one
+    assert_eq!(1120, distance);
+}
+
+#[test]
+fn test_race() {
+    let deers: Vec<Reindeer> = [
+        "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.",
+        "Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.",
+    ]
+    .iter()
+    .map(|desc| Reindeer::from_description(desc).unwrap())
+    .collect();
+
+    let mut race = Race::new(&deers);
+    let first = race.next().unwrap();
+    assert_eq!(
+        (1, vec![14, 16], vec![0, 1]),
+        (first.second, first.distances, first.points)
+    );
+    // Comet overtakes Dancer while Dancer is resting
+    assert_eq!(vec![1], race.nth(137).unwrap().leaders());
+    assert_eq!(vec![0], race.next().unwrap().leaders());
+
+    // Reindeer level with each other all get a point
+    let mut twins = deers.clone();
+    twins[1] = Reindeer {
+        name: "Twin".to_owned(),
+        ..deers[0].clone()
+    };
+    let end = Race::new(&twins).nth(999).unwrap();
+    assert_eq!(
+        (vec![1120, 1120], vec![1000, 1000]),
+        (end.distances, end.points)
+    );
+    assert_eq!(2, find_fastest(&twins, 1000).0.len());
+
+    let csv = timeline_csv(&deers, 1000);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(1001, lines.len());
+    assert_eq!(
+        "second,Comet distance,Comet points,Dancer distance,Dancer points",
+        lines[0]
+    );
+    assert_eq!("1,14,0,16,1", lines[1]);
+    assert_eq!("1000,1120,312,1056,689", lines[1000]);
+}
+
+#[test]
+fn test_csv_setting() {
+    let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\n";
+    let deers = Day14.parse(input).unwrap();
+
+    let csv = exported("csv", |settings| Day14.export(&deers, settings));
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(timeline_csv(&deers, RACE), csv);
+    assert_eq!(Some("2503,"), csv.lines().last().map(|row| &row[..5]));
+    assert_eq!(
+        Err(ParseError::new("json", "\"csv\"")),
+        Day14
+            .parse_with(input, &[Setting::new("json", "-")])
+            .map(|_| ())
+    );
 }
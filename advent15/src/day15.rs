@@ -43,13 +43,11 @@ zero.
 Given the ingredients in your kitchen and their properties, what is the total
 score of the highest-scoring cookie you can make?
 */
-extern crate regex;
-
-use std::io::{self, BufRead};
-use self::regex::Regex;
+use advent_core::grammar::{after, name, number, parse_line};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 #[derive(Debug, Clone)]
-struct Ingredient {
+pub struct Ingredient {
     name: String,
     capacity: i32,
     durability: i32,
@@ -58,147 +56,319 @@ struct Ingredient {
     calories: i32,
 }
 
-// Sprinkles: capacity 2, durability 0, flavor -2, texture 0, calories 3
-fn parse_ingredient(s: &str) -> Option<Ingredient> {
-    lazy_static! {
-        static ref R_ING: Regex =
-            Regex::new(r"(\w+): capacity (-?\d+), durability (-?\d+), flavor (-?\d+), texture (-?\d+), calories (-?\d+)").unwrap();
+/// One of the properties of an ingredient, which add up over a recipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Capacity,
+    Durability,
+    Flavor,
+    Texture,
+    Calories,
+}
+
+// The properties multiplied together for a recipe's score
+const SCORED: [Property; 4] = [
+    Property::Capacity,
+    Property::Durability,
+    Property::Flavor,
+    Property::Texture,
+];
+
+const ALL: [Property; 5] = [
+    Property::Capacity,
+    Property::Durability,
+    Property::Flavor,
+    Property::Texture,
+    Property::Calories,
+];
+
+// The total of each property, in the order of `ALL`
+type Totals = [i32; 5];
+
+impl Ingredient {
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
-    if R_ING.is_match(s) {
-        for cap in R_ING.captures_iter(s) {
-            if let Ok(capacity) = cap[2].to_owned().parse::<i32>() {
-                if let Ok(durability) = cap[3].to_owned().parse::<i32>() {
-                    if let Ok(flavor) = cap[4].to_owned().parse::<i32>() {
-                        if let Ok(texture) = cap[5].to_owned().parse::<i32>() {
-                            if let Ok(calories) = cap[6].to_owned().parse::<i32>() {
-                                let ing = Ingredient {
-                                    name: cap[1].to_owned(),
-                                    capacity: capacity,
-                                    durability: durability,
-                                    flavor: flavor,
-                                    texture: texture,
-                                    calories: calories,
-                                };
-
-                                return Some(ing);
-                            }
-                        }
-                    }
-                }
-            }
+    pub fn property(&self, property: Property) -> i32 {
+        match property {
+            Property::Capacity => self.capacity,
+            Property::Durability => self.durability,
+            Property::Flavor => self.flavor,
+            Property::Texture => self.texture,
+            Property::Calories => self.calories,
         }
     }
+}
 
-    None
+// Sprinkles: capacity 2, durability 0, flavor -2, texture 0, calories 3
+pub fn parse_ingredient(s: &str) -> Result<Ingredient, ParseError> {
+    let ingredient = (
+        name(),
+        after(": capacity", number()),
+        after(", durability", number()),
+        after(", flavor", number()),
+        after(", texture", number()),
+        after(", calories", number()),
+    );
+    let (name, capacity, durability, flavor, texture, calories) = parse_line(s, ingredient)?;
+
+    Ok(Ingredient {
+        name,
+        capacity,
+        durability,
+        flavor,
+        texture,
+        calories,
+    })
 }
 
-fn calculate_total(ingredients: &Vec<Ingredient>, amounts: &Vec<i32>) -> Option<i32> {
+fn calculate_total(ingredients: &[Ingredient], amounts: &[i32]) -> Option<i64> {
     if ingredients.len() != amounts.len() {
         return None;
     }
 
-    let mut capacity = 0;
-    let mut durability = 0;
-    let mut flavor = 0;
-    let mut texture = 0;
-    let mut _calories = 1;
-
-    for (ing, amount) in ingredients.iter().zip(amounts.iter()) {
-        capacity += ing.capacity * amount;
-        durability += ing.durability * amount;
-        flavor += ing.flavor * amount;
-        texture += ing.texture * amount;
-        // _calories += ing.calories * amount;
-    }
+    let score = SCORED.iter().fold(1, |score, &property| {
+        let total: i32 = ingredients
+            .iter()
+            .zip(amounts)
+            .map(|(ing, amount)| ing.property(property) * amount)
+            .sum();
+        score * total.max(0) as i64
+    });
+    Some(score)
+}
 
-    if capacity < 0 {
-        capacity = 0;
-    }
-    if durability < 0 {
-        durability = 0;
+/// The lowest and the highest total of a property a recipe may have.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub property: Property,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl Bound {
+    pub fn exactly(property: Property, total: i32) -> Bound {
+        Bound {
+            property,
+            min: Some(total),
+            max: Some(total),
+        }
     }
-    if flavor < 0 {
-        flavor = 0;
+
+    pub fn at_least(property: Property, total: i32) -> Bound {
+        Bound {
+            property,
+            min: Some(total),
+            max: None,
+        }
     }
-    if texture < 0 {
-        texture = 0;
+
+    pub fn at_most(property: Property, total: i32) -> Bound {
+        Bound {
+            property,
+            min: None,
+            max: Some(total),
+        }
     }
-    if _calories < 0 {
-        _calories = 0;
+
+    // Whether any total from lo to hi is within the bound
+    fn allows(&self, lo: i32, hi: i32) -> bool {
+        self.min.is_none_or(|min| hi >= min) && self.max.is_none_or(|max| lo <= max)
     }
+}
 
-    Some(capacity * durability * flavor * texture * _calories)
+/// How many teaspoons a recipe has, and the bounds its properties must keep
+/// to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraints {
+    pub teaspoons: i32,
+    pub bounds: Vec<Bound>,
 }
 
-// How to divide given num between given slots
-fn ration(num: i32, slots: i32) -> Vec<Vec<i32>> {
-    if num < 0 {
-        return vec![];
-    }
-    if slots <= 1 {
-        return vec![vec![num]];
-    } else {
-        let mut rs: Vec<Vec<i32>> = vec![];
+/// The teaspoons of each ingredient, in the order they were given, and the
+/// score of the cookie they make.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recipe {
+    pub amounts: Vec<i32>,
+    pub score: i64,
+}
+
+// The search for the best recipes, an ingredient at a time. Each property
+// can only change by so much per teaspoon of the ingredients still to come,
+// which bounds both the totals a partial recipe can reach and its score.
+struct Search<'a> {
+    ingredients: &'a [Ingredient],
+    bounds: &'a [Bound],
+    // The least and the most of each property per teaspoon among the
+    // ingredients from each one on
+    least: Vec<Totals>,
+    most: Vec<Totals>,
+    count: usize,
+    // The best so far, best first
+    best: Vec<Recipe>,
+}
 
-        for n in 0..num + 1 {
-            let mut rss = ration(num - n, slots - 1);
+fn index(property: Property) -> usize {
+    property as usize
+}
 
-            for rsss in rss {
-                let mut rssss = rsss;
-                rssss.push(n);
-                rs.push(rssss);
+impl<'a> Search<'a> {
+    fn new(ingredients: &'a [Ingredient], bounds: &'a [Bound], count: usize) -> Search<'a> {
+        let n = ingredients.len();
+        let mut least = vec![[i32::MAX; 5]; n + 1];
+        let mut most = vec![[i32::MIN; 5]; n + 1];
+        for i in (0..n).rev() {
+            for &property in &ALL {
+                let (p, value) = (index(property), ingredients[i].property(property));
+                least[i][p] = least[i + 1][p].min(value);
+                most[i][p] = most[i + 1][p].max(value);
             }
         }
 
-        return rs;
+        Search {
+            ingredients,
+            bounds,
+            least,
+            most,
+            count,
+            best: vec![],
+        }
+    }
+
+    // Whether the ingredients from `next` on can still bring the totals
+    // within bounds, and a score good enough to keep, with `left` teaspoons
+    fn promising(&self, next: usize, left: i32, totals: &Totals) -> bool {
+        let reach = |property: Property| {
+            let p = index(property);
+            if next == self.ingredients.len() {
+                (totals[p], totals[p])
+            } else {
+                (
+                    totals[p] + left * self.least[next][p],
+                    totals[p] + left * self.most[next][p],
+                )
+            }
+        };
+
+        let within = self.bounds.iter().all(|bound| {
+            let (lo, hi) = reach(bound.property);
+            bound.allows(lo, hi)
+        });
+        let most = SCORED.iter().fold(1, |score, &property| {
+            score * reach(property).1.max(0) as i64
+        });
+
+        within
+            && (self.best.len() < self.count
+                || self.best.last().is_none_or(|worst| most > worst.score))
+    }
+
+    fn keep(&mut self, recipe: Recipe) {
+        let at = self
+            .best
+            .iter()
+            .position(|other| recipe.score > other.score)
+            .unwrap_or(self.best.len());
+        self.best.insert(at, recipe);
+        self.best.truncate(self.count);
     }
-}
 
-fn calculate_optimal(ingredients: &Vec<Ingredient>, amount: i32) -> Option<(i32, Vec<i32>)> {
-    let mut max_amount: Option<i32> = None;
-    let mut max_rations: Option<Vec<i32>> = None;
-
-    for rs in ration(amount, ingredients.len() as i32) {
-        let total = calculate_total(ingredients, &rs);
-
-        match total {
-            Some(val) => {
-                if max_amount == None {
-                    max_amount = Some(val);
-                    max_rations = Some(rs);
-                } else if val > max_amount.unwrap() {
-                    max_amount = Some(val);
-                    max_rations = Some(rs);
-                }
+    fn search(&mut self, amounts: &mut Vec<i32>, left: i32, totals: Totals) {
+        let i = amounts.len();
+        if i == self.ingredients.len() {
+            if let Some(score) = calculate_total(self.ingredients, amounts) {
+                self.keep(Recipe {
+                    amounts: amounts.clone(),
+                    score,
+                });
+            }
+            return;
+        }
+
+        // The last ingredient takes whatever is left
+        let first = if i + 1 == self.ingredients.len() {
+            left
+        } else {
+            0
+        };
+        for amount in first..left + 1 {
+            let mut totals = totals;
+            for &property in &ALL {
+                totals[index(property)] += amount * self.ingredients[i].property(property);
+            }
+
+            if self.promising(i + 1, left - amount, &totals) {
+                amounts.push(amount);
+                self.search(amounts, left - amount, totals);
+                amounts.pop();
             }
-            None => continue,
         }
     }
+}
 
-    match max_amount {
-        Some(val) => Some((val, max_rations.unwrap())),
-        None => None,
+/// The best `count` recipes within the constraints, best first, recipes
+/// with the same score being in order of their amounts. Whole parts of the
+/// search are skipped when no recipe in them could keep to the bounds or
+/// score well enough, and only the best recipes so far are kept, so any
+/// number of ingredients can be used.
+pub fn best_recipes(
+    ingredients: &[Ingredient],
+    constraints: &Constraints,
+    count: usize,
+) -> Vec<Recipe> {
+    if ingredients.is_empty() || constraints.teaspoons < 0 || count == 0 {
+        return vec![];
     }
+
+    let mut search = Search::new(ingredients, &constraints.bounds, count);
+    search.search(&mut vec![], constraints.teaspoons, [0; 5]);
+    search.best
+}
+
+// The best cookie using `amount` teaspoons, optionally only considering the
+// recipes with exactly the given number of calories
+fn calculate_optimal(
+    ingredients: &[Ingredient],
+    amount: i32,
+    calories: Option<i32>,
+) -> Option<(i64, Vec<i32>)> {
+    let constraints = Constraints {
+        teaspoons: amount,
+        bounds: calories
+            .map(|calories| Bound::exactly(Property::Calories, calories))
+            .into_iter()
+            .collect(),
+    };
+    let best = best_recipes(ingredients, &constraints, 1).pop()?;
+
+    let names: Vec<String> = ingredients
+        .iter()
+        .zip(&best.amounts)
+        .map(|(ing, amount)| format!("{} {}", amount, ing.name()))
+        .collect();
+    debug!("{} scores {}", names.join(", "), best.score);
+    Some((best.score, best.amounts))
 }
 
-pub fn problem() {
-    let mut ings : Vec<Ingredient> = vec![];
+pub struct Day15;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day15 {
+    type Input = Vec<Ingredient>;
 
-        if let Some(ing) = parse_ingredient(&sline) {
-            ings.push(ing);
-        } else {
-            println!("Failed to parse: {}", sline);
-        }
+    fn parse(&self, input: &str) -> Result<Vec<Ingredient>, ParseError> {
+        parse_lines(input, parse_ingredient)
+    }
+
+    fn part1(&self, input: &Vec<Ingredient>) -> Answer {
+        calculate_optimal(input, 100, None).map(|(val, _)| val).into()
     }
 
-    let optimal = calculate_optimal(&ings, 100);
-    println!("There are {} ingredients", ings.len());
-    println!("Optimal: {:?}", optimal);
+    fn part2(&self, input: &Vec<Ingredient>) -> Answer {
+        calculate_optimal(input, 100, Some(500))
+            .map(|(val, _)| val)
+            .into()
+    }
 }
 
 #[test]
@@ -223,7 +393,7 @@ fn test_calculate_example() {
 
     let ingredients = vec![butterscotch, cinnamon];
 
-    let total = calculate_total(&ingredients, &vec![44, 56]);
+    let total = calculate_total(&ingredients, &[44, 56]);
     assert_eq!(62842880, total.unwrap_or(0));
 }
 
@@ -249,34 +419,100 @@ fn test_calculate_optimal_example() {
 
     let ingredients = vec![butterscotch, cinnamon];
 
-    if let Some((opt_val, opt_rations)) = calculate_optimal(&ingredients, 100) {
+    if let Some((opt_val, opt_rations)) = calculate_optimal(&ingredients, 100, None) {
         assert_eq!(62842880, opt_val);
         assert_eq!(opt_rations, vec![44, 56]);
     } else {
         assert!(false);
     }
+
+    // Only the 500 calorie cookies
+    let (opt_val, opt_rations) =
+        calculate_optimal(&ingredients, 100, Some(500)).expect("a 500 calorie recipe");
+    assert_eq!(57600000, opt_val);
+    assert_eq!(opt_rations, vec![40, 60]);
 }
 
 #[test]
 fn test_parsing_ingredients() {
     assert!(
         parse_ingredient("Sprinkles: capacity 2, durability 0, flavor -2, texture 0, calories 3")
-            .is_some()
+            .is_ok()
     );
 
     assert!(
         parse_ingredient(
             "Butterscotch: capacity 0, durability 5, flavor -3, texture 0, calories 3"
-        ).is_some()
+        ).is_ok()
     );
 
     assert!(
         parse_ingredient("Chocolate: capacity 0, durability 0, flavor 5, texture -1, calories 8")
-            .is_some()
+            .is_ok()
     );
 
     assert!(
         parse_ingredient("Candy: capacity 0, durability -1, flavor 0, texture 5, calories 8")
-            .is_some()
+            .is_ok()
     );
 }
+
+#[test]
+fn test_best_recipes() {
+    let ingredients: Vec<Ingredient> = [
+        "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8",
+        "Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3",
+        "Sugar: capacity 3, durability 0, flavor 0, texture -3, calories 2",
+    ]
+    .iter()
+    .map(|line| parse_ingredient(line).unwrap())
+    .collect();
+
+    let constraints = Constraints {
+        teaspoons: 100,
+        bounds: vec![],
+    };
+    let top: Vec<Vec<i32>> = best_recipes(&ingredients[..2], &constraints, 3)
+        .into_iter()
+        .map(|recipe| recipe.amounts)
+        .collect();
+    assert_eq!(vec![vec![44, 56], vec![43, 57], vec![45, 55]], top);
+
+    // The same as trying every recipe
+    let constraints = Constraints {
+        teaspoons: 30,
+        bounds: vec![
+            Bound::at_most(Property::Calories, 150),
+            Bound::at_least(Property::Texture, 10),
+        ],
+    };
+    let mut every = vec![];
+    for a in 0..31 {
+        for b in 0..31 - a {
+            let amounts = vec![a, b, 30 - a - b];
+            let total = |property| {
+                (0..3)
+                    .map(|i| ingredients[i].property(property) * amounts[i])
+                    .sum::<i32>()
+            };
+            if total(Property::Calories) <= 150 && total(Property::Texture) >= 10 {
+                let score = calculate_total(&ingredients, &amounts).unwrap();
+                every.push(Recipe { amounts, score });
+            }
+        }
+    }
+    every.sort_by_key(|recipe| std::cmp::Reverse(recipe.score));
+    every.truncate(4);
+    assert_eq!(every, best_recipes(&ingredients, &constraints, 4));
+    assert_eq!(vec![12, 18, 0], every[0].amounts);
+
+    assert!(best_recipes(
+        &ingredients,
+        &Constraints {
+            teaspoons: 30,
+            bounds: vec![Bound::exactly(Property::Calories, 1)]
+        },
+        4
+    )
+    .is_empty());
+}
@@ -47,16 +47,16 @@ missing from your list aren't zero - you simply don't remember the value.
 What is the number of the Sue that got you the gift?
 */
 
-extern crate regex;
-
-use self::regex::Regex;
-use std::io::{self, BufRead};
 use std::collections::HashMap;
+use advent_core::grammar::{after, name, number, parse_line, separated, Parser};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 type Info = HashMap<String, i32>;
 
-#[derive(Debug)]
-struct Aunt {
+const TICKER_TAPE: &str = "children: 3, cats: 7, samoyeds: 2, pomeranians: 3, akitas: 0, vizslas: 0, goldfish: 5, trees: 3, cars: 2, perfumes: 1";
+
+#[derive(Debug, Clone)]
+pub struct Aunt {
     name: String,
     info: Info,
 }
@@ -73,91 +73,111 @@ fn check_compatibility(aunt: &Aunt, check: &Info) -> bool {
     true
 }
 
-fn eliminate_non_matching(info: &Info, aunts: &mut Vec<Aunt>) {
-    aunts.retain(|aunt| check_compatibility(&aunt, info));
-}
-
-fn parse_info(s: &String) -> Option<Info> {
-    lazy_static! {
-        static ref R_PROPERTY: Regex = Regex::new(r"(\w+): (\d+)").unwrap();
-    }
-
-    if R_PROPERTY.is_match(s) {
-        let mut props_map = Info::new();
-
-        for cap in R_PROPERTY.captures_iter(s) {
-            let prop_name = cap[1].to_owned();
-            let prop_value = cap[2].to_owned();
+// Like check_compatibility, but the cats and trees readings are lower bounds
+// and the pomeranians and goldfish readings upper bounds
+fn check_ranges(aunt: &Aunt, check: &Info) -> bool {
+    for (key, val) in &aunt.info {
+        if let Some(check_val) = check.get(key) {
+            let compatible = match key.as_str() {
+                "cats" | "trees" => val > check_val,
+                "pomeranians" | "goldfish" => val < check_val,
+                _ => val == check_val,
+            };
 
-            if let Ok(val) = prop_value.parse::<i32>() {
-                props_map.insert(prop_name, val);
+            if !compatible {
+                return false;
             }
         }
+    }
+
+    true
+}
 
-        return Some(props_map);
+// The number of the only aunt matching the ticker tape by `check`
+fn find_aunt<F>(aunts: &[Aunt], check: F) -> Answer
+where
+    F: Fn(&Aunt, &Info) -> bool,
+{
+    let match_info = parse_info(TICKER_TAPE).unwrap();
+    let matching: Vec<&Aunt> = aunts.iter().filter(|aunt| check(aunt, &match_info)).collect();
+
+    // Only a single aunt should match the ticker tape
+    if matching.len() == 1 {
+        aunt_number(matching[0]).into()
+    } else {
+        Answer::Unsolved
     }
+}
 
-    None
+fn parse_info(s: &str) -> Result<Info, ParseError> {
+    parse_line(s, properties())
 }
 
-fn parse_aunt(s: &String) -> Option<Aunt> {
-    if let Some(idx) = s.find(':') {
-        let name: String = s.chars().take(idx).collect();
-        let rest: String = s.chars().skip(idx + 1).collect();
+// Properties like "cats: 7, trees: 3"
+fn properties() -> impl Parser<Info> {
+    separated((name(), after(":", number())), ",").map(|props| props.into_iter().collect())
+}
 
-        if let Some(props_map) = parse_info(&rest) {
-            let aunt = Aunt {
-                name: name,
-                info: props_map,
-            };
+fn parse_aunt(s: &str) -> Result<Aunt, ParseError> {
+    let aunt = (name(), number::<i32>(), after(":", properties()));
+    let (name, number, info) = parse_line(s, aunt)?;
 
-            return Some(aunt);
-        }
-    } else {
-        println!("Error: No match");
-    }
+    Ok(Aunt {
+        name: format!("{} {}", name, number),
+        info,
+    })
+}
 
-    None
+// The aunt's number is the last part of her name, like "Sue 213"
+fn aunt_number(aunt: &Aunt) -> Option<i32> {
+    aunt.name
+        .split_whitespace()
+        .last()
+        .and_then(|n| n.parse::<i32>().ok())
 }
 
-pub fn problem() {
-    let mut aunt_info: Vec<Aunt> = vec![];
+pub struct Day16;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day16 {
+    type Input = Vec<Aunt>;
 
-        if let Some(aunt) = parse_aunt(&sline) {
-            aunt_info.push(aunt);
-        } else {
-            println!("Invalid aunt info: {}", sline);
-        }
+    fn parse(&self, input: &str) -> Result<Vec<Aunt>, ParseError> {
+        parse_lines(input, parse_aunt)
     }
 
-    let match_info =
-        parse_info(&"children: 3, cats: 7, samoyeds: 2, pomeranians: 3, akitas: 0, vizslas: 0, goldfish: 5, trees: 3, cars: 2, perfumes: 1".to_owned()).unwrap();
+    fn part1(&self, input: &Vec<Aunt>) -> Answer {
+        find_aunt(input, check_compatibility)
+    }
 
-    eliminate_non_matching(&match_info, &mut aunt_info);
-    println!("*** Matching Aunts ***");
-    for a in aunt_info {
-        println!("{:?}", a);
+    fn part2(&self, input: &Vec<Aunt>) -> Answer {
+        find_aunt(input, check_ranges)
     }
 }
 
 #[test]
 fn test_parse_aunt() {
-    let aunt_op = parse_aunt(&"Sue 8: perfumes: 7, children: 2, cats: 1".to_owned());
-    assert!(aunt_op.is_some());
+    let aunt_op = parse_aunt("Sue 8: perfumes: 7, children: 2, cats: 1");
+    assert!(aunt_op.is_ok());
     let aunt = aunt_op.unwrap();
     assert_eq!(aunt.name, "Sue 8");
 }
 
 #[test]
 fn test_parse_info() {
-    let match_info =
-        parse_info(&"children: 3, cats: 7, samoyeds: 2, pomeranians: 3, akitas: 0, vizslas: 0, goldfish: 5, trees: 3, cars: 2, perfumes: 1".to_owned());
+    let match_info = parse_info(TICKER_TAPE);
 
-    assert!(match_info.is_some());
+    assert!(match_info.is_ok());
     // There should be 8 properties
     assert_eq!(match_info.unwrap().len(), 10);
 }
+
+#[test]
+fn test_check_ranges() {
+    let info = parse_info(TICKER_TAPE).unwrap();
+    let aunt = |s: &str| parse_aunt(s).unwrap();
+
+    assert!(check_ranges(&aunt("Sue 1: cats: 8, goldfish: 4, cars: 2"), &info));
+    assert!(!check_ranges(&aunt("Sue 2: cats: 7, goldfish: 4"), &info));
+    assert!(!check_ranges(&aunt("Sue 3: trees: 4, pomeranians: 3"), &info));
+    assert!(check_compatibility(&aunt("Sue 4: cats: 7, goldfish: 5"), &info));
+}
@@ -17,7 +17,7 @@ you need to store 25 liters, there are four ways to do it:
 Filling all containers entirely, how many different combinations of containers
 can exactly fit all 150 liters of eggnog?
 */
-use std::io::{self, BufRead};
+use advent_core::{parse_lines, parse_number, Answer, ParseError, Solution};
 
 /*
 What is the most Rusty way to following Haskell function?
@@ -42,22 +42,54 @@ fn count_ways(num: i32, containers: &[i32]) -> i32 {
     count_ways(num - head[0], tail) + count_ways(num, tail)
 }
 
-pub fn problem() {
-    let mut containers: Vec<i32> = vec![];
-    let amount = 150;
+// Like count_ways, but keeping the number of ways apart by how many
+// containers they use: the result at index i counts the ways with i containers
+fn count_ways_by_size(num: i32, containers: &[i32]) -> Vec<i32> {
+    if num == 0 {
+        return vec![1];
+    }
+
+    if num < 0 || containers.is_empty() {
+        return vec![];
+    }
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+    let (head, tail) = containers.split_at(1);
+    let mut ways = count_ways_by_size(num, tail);
 
-        if let Ok(c) = sline.parse::<i32>() {
-            containers.push(c);
+    // Using the head container shifts every way up by one container
+    for (i, n) in count_ways_by_size(num - head[0], tail).into_iter().enumerate() {
+        if ways.len() <= i + 1 {
+            ways.resize(i + 2, 0);
         }
+        ways[i + 1] += n;
+    }
+
+    ways
+}
+
+// The number of ways to fill the fewest containers possible
+fn count_minimal_ways(num: i32, containers: &[i32]) -> Option<i32> {
+    count_ways_by_size(num, containers)
+        .into_iter()
+        .find(|&n| n > 0)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>, ParseError> {
+        parse_lines(input, |line| parse_number(line.trim()).map_err(|e| e.within(line)))
+    }
+
+    fn part1(&self, input: &Vec<i32>) -> Answer {
+        count_ways(150, input).into()
     }
 
-    println!("Containers: {:?}", containers);
-    let ways = count_ways(amount, &containers);
-    println!("There are {} ways to put {} into containers", ways, amount);
+    fn part2(&self, input: &Vec<i32>) -> Answer {
+        count_minimal_ways(150, input).into()
+    }
 }
 
 #[test]
@@ -65,3 +97,10 @@ fn test_count_ways_example() {
     let ways = count_ways(25, &vec![20, 15, 10, 5, 5]);
     assert_eq!(4, ways);
 }
+
+#[test]
+fn test_count_minimal_ways_example() {
+    assert_eq!(vec![0, 0, 3, 1], count_ways_by_size(25, &[20, 15, 10, 5, 5]));
+    assert_eq!(Some(3), count_minimal_ways(25, &[20, 15, 10, 5, 5]));
+    assert_eq!(None, count_minimal_ways(100, &[20, 15]));
+}
@@ -0,0 +1,392 @@
+// Cellular automata on a grid, of which day 18's lights are Conway's game of
+// life. Each generation, every cell counts its live neighbours and the rule
+// decides from the count whether it is born, survives or dies. The counts are
+// sums of the board shifted by each neighbour's offset, read from a copy of
+// the board with a border as deep as the neighbourhood reaches, so the edges
+// need no special cases: the border is dead for a bounded board and the
+// opposite side of the board for a toroidal one.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use advent_core::ParseError;
+use ndarray::prelude::*;
+
+/// Which neighbour counts bring a dead cell to life and which keep a live one
+/// alive, written like "B3/S23" for Conway's game of life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    // A bit set for each count
+    birth: u32,
+    survival: u32,
+}
+
+impl Rule {
+    /// Whether a cell is alive in the next generation.
+    pub fn next(&self, alive: bool, neighbours: u32) -> bool {
+        let counts = if alive { self.survival } else { self.birth };
+        neighbours < 32 && counts >> neighbours & 1 == 1
+    }
+}
+
+// The neighbour counts listed after the letter, starting at the column
+fn counts(text: &str, letter: char, column: usize) -> Result<u32, ParseError> {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(c) if c.eq_ignore_ascii_case(&letter) => {}
+        _ => return Err(ParseError::new(text, format!("{:?}", letter.to_string())).at(0, column)),
+    }
+
+    let mut counts = 0;
+    for (i, c) in chars.enumerate() {
+        match c.to_digit(10) {
+            Some(n) => counts |= 1 << n,
+            None => {
+                return Err(
+                    ParseError::new(c.to_string(), "a neighbour count").at(0, column + i + 1)
+                )
+            }
+        }
+    }
+    Ok(counts)
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Rule, ParseError> {
+        let slash = match text.find('/') {
+            Some(slash) => slash,
+            None => return Err(ParseError::new(text, "a rule like B3/S23")),
+        };
+        Ok(Rule {
+            birth: counts(&text[..slash], 'B', 1)?,
+            survival: counts(&text[slash + 1..], 'S', text[..slash].chars().count() + 2)?,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: u32| -> String {
+            (0..10)
+                .filter(|n| counts >> n & 1 == 1)
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+/// The cells counted as a cell's neighbours.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Neighbourhood {
+    /// Those within the distance along both the rows and the columns, the
+    /// eight surrounding the cell at a distance of 1.
+    Moore(usize),
+    /// Those within the distance counting steps along rows and columns, the
+    /// four beside the cell at a distance of 1.
+    VonNeumann(usize),
+    /// Those at the given (row, column) offsets from the cell.
+    Offsets(Vec<(isize, isize)>),
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> Vec<(isize, isize)> {
+        let square = |reach: usize| {
+            let reach = reach as isize;
+            (-reach..reach + 1)
+                .flat_map(move |row| (-reach..reach + 1).map(move |col| (row, col)))
+                .filter(|&offset| offset != (0, 0))
+        };
+        match *self {
+            Neighbourhood::Moore(reach) => square(reach).collect(),
+            Neighbourhood::VonNeumann(reach) => square(reach)
+                .filter(|&(row, col)| (row.abs() + col.abs()) as usize <= reach)
+                .collect(),
+            Neighbourhood::Offsets(ref offsets) => offsets.clone(),
+        }
+    }
+}
+
+/// What lies beyond the edges of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// Nothing, as if the cells there were all dead.
+    Bounded,
+    /// The other side of the board, which wraps round in both directions.
+    Toroidal,
+}
+
+/// A grid of cells, each alive or dead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    cells: Array2<u8>,
+}
+
+impl Board {
+    /// A board with every cell dead.
+    pub fn new(rows: usize, cols: usize) -> Board {
+        Board {
+            cells: Array2::zeros((rows, cols)),
+        }
+    }
+
+    /// A board from its rows, with '#' for a live cell and '.' for a dead one.
+    /// Any other characters are skipped. None if the number of cells doesn't
+    /// fit the size.
+    pub fn parse(rows: usize, cols: usize, text: &[u8]) -> Option<Board> {
+        let cells: Vec<u8> = text
+            .iter()
+            .filter_map(|&c| match c {
+                b'#' => Some(1),
+                b'.' => Some(0),
+                _ => None,
+            })
+            .collect();
+
+        Array2::from_shape_vec((rows, cols), cells)
+            .ok()
+            .map(|cells| Board { cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+
+    pub fn is_alive(&self, row: usize, col: usize) -> bool {
+        self.cells[[row, col]] > 0
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, alive: bool) {
+        self.cells[[row, col]] = alive as u8;
+    }
+
+    pub fn count_alive(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell > 0).count()
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.genrows() {
+            for &cell in row {
+                write!(f, "{}", if cell > 0 { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a board's generations start repeating: the generation which first
+/// comes round again, and how many generations it takes. A board which stays
+/// the same has a period of 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+/// A rule, the neighbourhood it counts and what lies beyond the board's
+/// edges, along with any cells pinned alive or dead whatever their
+/// neighbours.
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    rule: Rule,
+    offsets: Vec<(isize, isize)>,
+    edges: Edges,
+    pinned: Vec<(usize, usize, bool)>,
+}
+
+impl Automaton {
+    pub fn new(rule: Rule, neighbourhood: Neighbourhood, edges: Edges) -> Automaton {
+        Automaton {
+            rule,
+            offsets: neighbourhood.offsets(),
+            edges,
+            pinned: vec![],
+        }
+    }
+
+    /// Hold the cell alive or dead in every generation. A cell outside the
+    /// board is ignored.
+    pub fn pin(&mut self, row: usize, col: usize, alive: bool) {
+        self.pinned.push((row, col, alive));
+    }
+
+    fn hold_pinned(&self, board: &mut Board) {
+        for &(row, col, alive) in &self.pinned {
+            if let Some(cell) = board.cells.get_mut((row, col)) {
+                *cell = alive as u8;
+            }
+        }
+    }
+
+    /// Move the board on to its next generation.
+    pub fn step(&self, board: &mut Board) {
+        self.hold_pinned(board);
+        let (rows, cols) = board.cells.dim();
+        if rows == 0 || cols == 0 {
+            return;
+        }
+
+        let reach = self
+            .offsets
+            .iter()
+            .map(|&(row, col)| cmp::max(row.abs(), col.abs()))
+            .max()
+            .unwrap_or(0);
+        let padded = Array2::from_shape_fn(
+            (rows + 2 * reach as usize, cols + 2 * reach as usize),
+            |(i, j)| {
+                let (row, col) = (i as isize - reach, j as isize - reach);
+                let inside = 0 <= row && row < rows as isize && 0 <= col && col < cols as isize;
+                match self.edges {
+                    _ if inside => board.cells[[row as usize, col as usize]] as u32,
+                    Edges::Bounded => 0,
+                    Edges::Toroidal => {
+                        let row = row.rem_euclid(rows as isize) as usize;
+                        let col = col.rem_euclid(cols as isize) as usize;
+                        board.cells[[row, col]] as u32
+                    }
+                }
+            },
+        );
+
+        let mut neighbours: Array2<u32> = Array2::zeros((rows, cols));
+        for &(row, col) in &self.offsets {
+            let (i, j) = ((reach + row) as usize, (reach + col) as usize);
+            neighbours += &padded.slice(s![i..i + rows, j..j + cols]);
+        }
+
+        let rule = self.rule;
+        board.cells.zip_mut_with(&neighbours, |cell, &n| {
+            *cell = rule.next(*cell > 0, n) as u8
+        });
+        self.hold_pinned(board);
+    }
+
+    /// Move the board on by the given number of generations.
+    pub fn run(&self, board: &mut Board, generations: usize) {
+        self.hold_pinned(board);
+        for _ in 0..generations {
+            self.step(board);
+        }
+    }
+
+    /// Move the board on by the given number of generations, like `run`, but
+    /// as soon as a board comes round again, skip the whole turns of the
+    /// cycle it is in and only run what is left over. Returns the cycle if
+    /// one was found before the last generation.
+    pub fn fast_forward(&self, board: &mut Board, generations: usize) -> Option<Cycle> {
+        self.hold_pinned(board);
+        let mut seen: HashMap<Board, usize> = HashMap::new();
+
+        for generation in 0..generations {
+            if let Some(start) = seen.insert(board.clone(), generation) {
+                let cycle = Cycle {
+                    start,
+                    period: generation - start,
+                };
+                debug!(
+                    "The board repeats every {} generations from generation {}",
+                    cycle.period, cycle.start
+                );
+                self.run(board, (generations - generation) % cycle.period);
+                return Some(cycle);
+            }
+            self.step(board);
+        }
+        None
+    }
+}
+
+#[test]
+fn test_rule() {
+    let highlife: Rule = "B36/S23".parse().unwrap();
+    assert!(highlife.next(false, 6));
+    assert!(highlife.next(true, 2));
+    assert!(!highlife.next(true, 6));
+    assert_eq!("B36/S23", highlife.to_string());
+    assert_eq!("B2/S", "b2/s".parse::<Rule>().unwrap().to_string());
+
+    assert_eq!(
+        Err(ParseError::new("x", "a neighbour count").at(0, 7)),
+        "B3/S23x".parse::<Rule>()
+    );
+    assert_eq!(
+        Err(ParseError::new("23", "\"S\"").at(0, 4)),
+        "B3/23".parse::<Rule>()
+    );
+}
+
+#[test]
+fn test_toroidal_glider() {
+    let glider = b".#....\
+                   ..#...\
+                   ###...\
+                   ......\
+                   ......\
+                   ......";
+    let life = Automaton::new(
+        "B3/S23".parse().unwrap(),
+        Neighbourhood::Moore(1),
+        Edges::Toroidal,
+    );
+    let mut board = Board::parse(6, 6, glider).unwrap();
+    let start = board.clone();
+
+    // The glider moves one cell diagonally every 4 generations, so wraps
+    // round both ways after 24
+    life.run(&mut board, 12);
+    assert_eq!(5, board.count_alive());
+    assert_ne!(start, board);
+    life.run(&mut board, 12);
+    assert_eq!(start, board);
+
+    // Whereas on a bounded board it ends up as a block in the corner
+    let bounded = Automaton::new(
+        "B3/S23".parse().unwrap(),
+        Neighbourhood::Moore(1),
+        Edges::Bounded,
+    );
+    bounded.run(&mut board, 24);
+    assert_eq!(4, board.count_alive());
+    assert!(board.is_alive(4, 4) && board.is_alive(5, 5));
+}
+
+#[test]
+fn test_cycles() {
+    let life = Automaton::new(
+        "B3/S23".parse().unwrap(),
+        Neighbourhood::Moore(1),
+        Edges::Bounded,
+    );
+    let blinker = b".....\
+                    .....\
+                    .###.\
+                    .....\
+                    .....";
+    let mut board = Board::parse(5, 5, blinker).unwrap();
+    let cycle = life.fast_forward(&mut board, 1_000_000_001);
+    assert_eq!(
+        Some(Cycle {
+            start: 0,
+            period: 2
+        }),
+        cycle
+    );
+    assert!(board.is_alive(1, 2) && !board.is_alive(2, 1));
+
+    // Too few generations to see the board come round again
+    let mut board = Board::parse(5, 5, blinker).unwrap();
+    assert_eq!(None, life.fast_forward(&mut board, 2));
+    assert_eq!(Board::parse(5, 5, blinker), Some(board));
+}
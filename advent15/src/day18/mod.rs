@@ -0,0 +1,211 @@
+// https://adventofcode.com/2015/day/18
+/*
+--- Day 18: Like a GIF For Your Yard ---
+
+After the million lights incident, the fire code has gotten stricter: now, at most ten thousand
+lights are allowed. You arrange them in a 100x100 grid.
+
+Never one to let you down, Santa again mails you instructions on the ideal lighting configuration.
+With so few lights, he says, you'll have to resort to animation.
+
+Start by setting your lights to the included initial configuration (your puzzle input). A # means
+"on", and a . means "off".
+
+Then, animate your grid in steps, where each step decides the next configuration based on the
+current one. Each light's next state (either on or off) depends on its current state and the
+current states of the eight lights adjacent to it (including diagonals). Lights on the edge of the
+grid might have fewer than eight neighbors; the missing ones always count as "off".
+
+For example, in a simplified 6x6 grid, the light marked A has the neighbors numbered 1 through 8,
+and the light marked B, which is on an edge, only has the neighbors marked 1 through 5:
+
+1B5...
+234...
+......
+..123.
+..8A4.
+..765.
+
+The state a light should have next is based on its current state (on or off) plus the number of
+neighbors that are on:
+
+- A light which is on stays on when 2 or 3 neighbors are on, and turns off otherwise.
+- A light which is off turns on if exactly 3 neighbors are on, and stays off otherwise.
+- All of the lights update simultaneously; they all consider the same current state before moving
+  to the next.
+
+Here's a few steps from an example configuration of another 6x6 grid:
+
+Initial state:
+.#.#.#
+...##.
+#....#
+..#...
+#.#..#
+####..
+
+After 1 step:
+..##..
+..##.#
+...##.
+......
+#.....
+#.##..
+
+After 2 steps:
+..###.
+......
+..###.
+......
+.#....
+.#....
+
+After 3 steps:
+...#..
+......
+...#..
+..##..
+......
+......
+
+After 4 steps:
+......
+......
+..##..
+..##..
+......
+......
+After 4 steps, this example has four lights on.
+
+In your grid of 100x100 lights, given your initial configuration, how many lights are on after 100
+steps?
+*/
+
+pub mod automaton;
+
+use advent_core::{parse_lines, Answer, ParseError, Solution};
+
+use self::automaton::{Automaton, Board, Edges, Neighbourhood};
+
+// The yard's lights follow Conway's game of life, with the lights beyond the
+// edges of the grid counting as off. With stuck corners, the four corner
+// lights are always on, whatever their neighbours are.
+fn yard(size: usize, stuck_corners: bool) -> Automaton {
+    let life = "B3/S23".parse().expect("a valid rule");
+    let mut yard = Automaton::new(life, Neighbourhood::Moore(1), Edges::Bounded);
+    if stuck_corners {
+        let last = size.saturating_sub(1);
+        for &(row, col) in &[(0, 0), (0, last), (last, 0), (last, last)] {
+            yard.pin(row, col, true);
+        }
+    }
+    yard
+}
+
+// Run the board for the given rounds, skipping ahead once the lights start
+// repeating themselves
+fn compute_board_state(
+    bsize: usize,
+    init: &[u8],
+    rounds: usize,
+    stuck_corners: bool,
+) -> Option<Board> {
+    let mut board = match Board::parse(bsize, bsize, init) {
+        Some(board) => board,
+        None => {
+            warn!("Error initialising board");
+            return None;
+        }
+    };
+
+    debug!("Initial board\n{}", board);
+    yard(bsize, stuck_corners).fast_forward(&mut board, rounds);
+
+    debug!("Final board after {} rounds\n{}", rounds, board);
+    debug!(
+        "There are {} lights on after {} rounds",
+        board.count_alive(),
+        rounds
+    );
+
+    Some(board)
+}
+
+pub struct Day18;
+
+// For the problem, we expect to read a square (100x100) board, so the board
+// size is given by the number of lines
+impl Solution for Day18 {
+    type Input = (usize, Vec<u8>);
+
+    fn parse(&self, input: &str) -> Result<(usize, Vec<u8>), ParseError> {
+        let mut width = None;
+        let rows = parse_lines(input, |line| {
+            let indent = line.len() - line.trim_start().len();
+            let row = line.trim();
+
+            if let Some((i, c)) = row.char_indices().find(|&(_, c)| c != '#' && c != '.') {
+                return Err(ParseError::new(c.to_string(), "\"#\" or \".\"").at(0, indent + i + 1));
+            }
+
+            let w = *width.get_or_insert(row.len());
+            if row.len() != w {
+                return Err(ParseError::new(row, format!("a row of {} lights", w)).at(0, indent + 1));
+            }
+
+            Ok(row.as_bytes().to_vec())
+        })?;
+
+        let size = rows.len();
+        if width.unwrap_or(0) != size {
+            return Err(ParseError::new(
+                format!("{} rows", size),
+                format!("a square board of {} rows", width.unwrap_or(0)),
+            ));
+        }
+
+        Ok((size, rows.concat()))
+    }
+
+    fn part1(&self, input: &(usize, Vec<u8>)) -> Answer {
+        let &(size, ref init_board) = input;
+        compute_board_state(size, init_board, 100, false)
+            .map(|board| board.count_alive())
+            .into()
+    }
+
+    fn part2(&self, input: &(usize, Vec<u8>)) -> Answer {
+        let &(size, ref init_board) = input;
+        compute_board_state(size, init_board, 100, true)
+            .map(|board| board.count_alive())
+            .into()
+    }
+}
+
+#[test]
+fn test_ndarray() {
+    let board = compute_board_state(6, b".#.#.#...##.#....#..#...#.#..#####..", 4, false);
+    assert!(board.is_some());
+    // In the given example, there should be 4 bulbs on after 4 rounds
+    assert_eq!(board.unwrap().count_alive(), 4);
+}
+
+#[test]
+fn test_stuck_corners() {
+    let board = compute_board_state(6, b".#.#.#...##.#....#..#...#.#..#####..", 5, true);
+    // With the corners stuck on, there are 17 bulbs on after 5 rounds
+    assert_eq!(board.map(|b| b.count_alive()), Some(17));
+}
+
+#[test]
+fn test_fast_forward() {
+    // The example settles into a block after 4 rounds
+    let init = b".#.#.#...##.#....#..#...#.#..#####..";
+    let mut board = Board::parse(6, 6, init).unwrap();
+    let cycle = yard(6, false).fast_forward(&mut board, 1_000_000_000);
+    assert_eq!(Some(automaton::Cycle { start: 4, period: 1 }), cycle);
+    assert_eq!(4, board.count_alive());
+
+    let board = compute_board_state(6, init, 1_000_000_000, true);
+    assert_eq!(board, compute_board_state(6, init, 100, true));
+}
@@ -71,10 +71,11 @@ split_match hay needle = go hay needle []
         starts_with (n:ns) (h:hs)  = (n == h) && starts_with ns hs
 */
 
-extern crate regex;
+use std::cmp::Reverse;
+use std::collections::HashSet;
 
-use self::regex::Regex;
-use std::io::{self, BufRead};
+use advent_core::grammar::{after, name, parse_line};
+use advent_core::{Answer, ParseError, Solution};
 
 // type Replacement = (String, String);
 
@@ -113,7 +114,7 @@ fn replacements(hay: &str, needle: &str, repl: &str, repls: &mut Vec<String>) {
     }
 }
 
-fn unique_replacements(hay: &str, rep_vec: &Vec<(String, String)>) -> Vec<String> {
+fn unique_replacements(hay: &str, rep_vec: &[(String, String)]) -> Vec<String> {
     let mut repls: Vec<String> = vec![];
 
     for &(ref needle, ref rep) in rep_vec {
@@ -126,66 +127,124 @@ fn unique_replacements(hay: &str, rep_vec: &Vec<(String, String)>) -> Vec<String
     repls
 }
 
-// By default we expect a string replacement rule. If it does not look like a
-// string replacement rule, then we take the given string as simply an input
-// string to be run the rules on
-fn parse_input(s: &String) -> Result<(String, String), String> {
-    lazy_static! {
-        static ref R_RULE: Regex = Regex::new(r"(\w+) => (\w+)").unwrap();
+// How many replacements make the molecule, starting from a single electron
+// "e", along the first way found of making it. Works backwards, undoing the
+// rule with the longest replacement found in the molecule first. Rules
+// turning the electron into something can only be undone as the very last
+// step. None if the molecule can't be made, or if some rule other than the
+// electron's doesn't add elements, as undoing the rules could then go round
+// in circles.
+fn reduction_steps(rules: &[(String, String)], molecule: &str) -> Option<usize> {
+    let circular = rules
+        .iter()
+        .any(|(needle, repl)| needle != "e" && elements(repl) <= elements(needle));
+    if circular {
+        return None;
     }
 
-    // Check if s matches a rule like <NEEDLE> => <REPL>
-    if R_RULE.is_match(s) {
-        for cap in R_RULE.captures_iter(s) {
-            let needle = cap[1].to_owned();
-            let repl = cap[2].to_owned();
-            return Ok((needle, repl));
-        }
-    }
+    let mut rules = rules.to_vec();
+    rules.sort_by_key(|rule| Reverse(rule.1.len()));
 
-    Err(s.to_owned())
+    reduce(&rules, molecule, &mut HashSet::new())
 }
 
-pub fn problem() {
-    // test_replace();
-    // return;
-
-    let mut repls: Vec<(String, String)> = vec![];
-    let mut input: String = String::new();
-
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+// The steps undoing the molecule back to "e", backing up to try the next
+// rule or the next place in the molecule when the first choice can't be
+// reduced any further. Every other rule takes elements out of the molecule,
+// so the search ends, and molecules found to be dead ends aren't tried again.
+fn reduce(
+    rules: &[(String, String)],
+    molecule: &str,
+    dead_ends: &mut HashSet<String>,
+) -> Option<usize> {
+    if molecule == "e" {
+        return Some(0);
+    }
+    if dead_ends.contains(molecule) {
+        return None;
+    }
 
-        // ignore empty lines
-        if sline.trim().is_empty() {
+    for (needle, repl) in rules {
+        if needle == "e" {
+            if molecule == repl {
+                return Some(1);
+            }
             continue;
         }
 
-        match parse_input(&sline.to_owned()) {
-            Ok(p) => repls.push(p),
-            Err(s) => {
-                input = s.to_owned();
-                break;
+        let found = molecule
+            .char_indices()
+            .map(|(at, _)| at)
+            .filter(|&at| molecule[at..].starts_with(repl.as_str()));
+        for at in found {
+            let reduced = format!(
+                "{}{}{}",
+                &molecule[..at],
+                needle,
+                &molecule[at + repl.len()..]
+            );
+            if let Some(steps) = reduce(rules, &reduced, dead_ends) {
+                return Some(steps + 1);
             }
         }
     }
 
-    if input.is_empty() {
-        println!("Error: empty input");
-        return;
-    }
-    if repls.is_empty() {
-        println!("Error: empty replacement rules");
-        return;
+    dead_ends.insert(molecule.to_owned());
+    None
+}
+
+// The elements in a molecule, each starting with a capital letter
+fn elements(molecule: &str) -> usize {
+    molecule.chars().filter(|c| c.is_uppercase()).count()
+}
+
+// Parse a string replacement rule like <NEEDLE> => <REPL>
+fn parse_rule(s: &str) -> Result<(String, String), ParseError> {
+    parse_line(s, (name(), after("=>", name())))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (Vec<(String, String)>, String);
+
+    // The replacement rules come first, and the last line is the molecule to
+    // run them on
+    fn parse(&self, data: &str) -> Result<(Vec<(String, String)>, String), ParseError> {
+        let lines: Vec<(usize, &str)> = data
+            .lines()
+            .enumerate()
+            .filter(|&(_, line)| !line.trim().is_empty())
+            .collect();
+
+        let (&(n, input), rules) = match lines.split_last() {
+            Some(split) => split,
+            None => return Err(ParseError::new("", "replacement rules and a molecule")),
+        };
+        if rules.is_empty() {
+            return Err(ParseError::new(input.trim(), "a rule like \"H => HO\"").at(n + 1, 1));
+        }
+
+        let repls = rules
+            .iter()
+            .map(|&(n, line)| parse_rule(line).map_err(|e| e.on_line(n + 1)))
+            .collect::<Result<Vec<(String, String)>, ParseError>>()?;
+        let input = input.trim().to_owned();
+
+        Ok((repls, input))
     }
 
-    println!("Input is: {}", input);
-    println!("The are {} replacement rules", repls.len());
+    fn part1(&self, data: &(Vec<(String, String)>, String)) -> Answer {
+        let &(ref repls, ref input) = data;
+        unique_replacements(input, repls).len().into()
+    }
 
-    let unique_reps = unique_replacements(&input, &repls);
-    println!("There are {} unique replacements", unique_reps.len());
-    println!("Unique replacements: {:?}", unique_reps);
+    // The puzzle's grammars make every way of reducing the molecule take the
+    // same number of steps, so the first one found is also the fewest
+    fn part2(&self, data: &(Vec<(String, String)>, String)) -> Answer {
+        let &(ref repls, ref input) = data;
+        reduction_steps(repls, input).into()
+    }
 }
 
 #[test]
@@ -224,3 +283,32 @@ fn test_unique_replacements() {
     let ureps = unique_replacements("HOH", &reps);
     assert_eq!(4, ureps.len());
 }
+
+#[test]
+fn test_reduction_steps() {
+    let reps = vec![
+        ("e".to_owned(), "H".to_owned()),
+        ("e".to_owned(), "O".to_owned()),
+        ("H".to_owned(), "HO".to_owned()),
+        ("H".to_owned(), "OH".to_owned()),
+        ("O".to_owned(), "HH".to_owned()),
+    ];
+
+    assert_eq!(Some(3), reduction_steps(&reps, "HOH"));
+    assert_eq!(Some(6), reduction_steps(&reps, "HOHOHO"));
+    assert_eq!(None, reduction_steps(&reps, "X"));
+
+    // Undoing Q => ABC first leads nowhere, so the search has to back up
+    let reps = vec![
+        ("e".to_owned(), "AD".to_owned()),
+        ("D".to_owned(), "BC".to_owned()),
+        ("Q".to_owned(), "ABC".to_owned()),
+    ];
+    assert_eq!(Some(2), reduction_steps(&reps, "ABC"));
+
+    // H => O could be undone forever, so part two can't be answered, but
+    // part one still can
+    let input = Day19.parse("e => H\nH => O\nHOH").unwrap();
+    assert_eq!(Answer::Number(2), Day19.part1(&input));
+    assert_eq!(Answer::Unsolved, Day19.part2(&input));
+}
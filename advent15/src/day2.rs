@@ -1,8 +1,11 @@
 // https://adventofcode.com/2015/day/2
 use std::cmp;
-use std::io::{self, BufRead};
 
-struct Dimensions {
+use advent_core::grammar::{after, number, parse_line};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
+
+#[derive(Debug, PartialEq)]
+pub struct Dimensions {
     width: i32,
     height: i32,
     length: i32,
@@ -25,51 +28,44 @@ fn required_paper(d: &Dimensions) -> i32 {
     surface_area(d) + slack(d)
 }
 
-fn parse_dimension(ds: &String) -> Result<Dimensions, String> {
-    let comps: Vec<&str> = ds.split("x").collect();
-    if comps.len() != 3 {
-        return Err("invalid dimensions - 3 components required".to_owned());
-    }
-
-    match comps[0].parse::<i32>() {
-        Ok(w) => match comps[1].parse::<i32>() {
-            Ok(l) => match comps[2].parse::<i32>() {
-                Ok(h) => Ok(Dimensions {
-                    width: w,
-                    height: h,
-                    length: l,
-                }),
-                Err(_) => Err("parsing height failed".to_owned()),
-            },
-            Err(_) => Err("parsing length failed".to_owned()),
-        },
-        Err(_) => Err("parsing width failed".to_owned()),
-    }
+// Ribbon to wrap the smallest perimeter of any face, plus the bow which takes
+// as much ribbon as the volume
+fn required_ribbon(d: &Dimensions) -> i32 {
+    let perimeter = 2 * cmp::min(d.length + d.width, cmp::min(d.width + d.height, d.height + d.length));
+    perimeter + d.length * d.width * d.height
 }
 
-fn compute_aggregate(ds: &Vec<String>) -> i32 {
-    let mut sum = 0;
+fn parse_dimension(ds: &str) -> Result<Dimensions, ParseError> {
+    let dimensions = (number(), after("x", number()), after("x", number()));
+    let (width, length, height) = parse_line(ds, dimensions)?;
 
-    for line in ds {
-        match parse_dimension(line) {
-            Ok(d) => sum += required_paper(&d),
-            Err(msg) => println!("error '{}' parsing {}", msg, line),
-        }
-    }
+    Ok(Dimensions {
+        width,
+        length,
+        height,
+    })
+}
 
-    sum
+fn compute_aggregate(ds: &[Dimensions]) -> i32 {
+    ds.iter().map(required_paper).sum()
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    let mut ds: Vec<String> = vec![];
+pub struct Day2;
 
-    for ln in stdin.lock().lines() {
-        ds.push(ln.unwrap());
+impl Solution for Day2 {
+    type Input = Vec<Dimensions>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Dimensions>, ParseError> {
+        parse_lines(input, parse_dimension)
     }
 
-    let agg = compute_aggregate(&ds);
-    println!("Total required: {}", agg);
+    fn part1(&self, input: &Vec<Dimensions>) -> Answer {
+        compute_aggregate(input).into()
+    }
+
+    fn part2(&self, input: &Vec<Dimensions>) -> Answer {
+        input.iter().map(required_ribbon).sum::<i32>().into()
+    }
 }
 
 #[test]
@@ -88,6 +84,8 @@ fn test_examples() {
 
     assert_eq!(58, required_paper(&d1));
     assert_eq!(43, required_paper(&d2));
+    assert_eq!(34, required_ribbon(&d1));
+    assert_eq!(14, required_ribbon(&d2));
 }
 
 #[test]
@@ -108,6 +106,18 @@ fn test_aggregate() {
 
     assert_eq!(
         expected,
-        compute_aggregate(&vec!["2x3x4".to_owned(), "1x1x10".to_owned()])
+        compute_aggregate(&Day2.parse("2x3x4\n1x1x10\n").unwrap())
     )
 }
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Err(ParseError::new("yx4", "\"x\"").at(2, 4)),
+        Day2.parse("2x3x4\n1x3yx4\n")
+    );
+    assert_eq!(
+        Err(ParseError::new("", "\"x\"").at(1, 4)),
+        Day2.parse("1x1")
+    );
+}
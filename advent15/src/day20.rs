@@ -38,8 +38,8 @@ the number in your puzzle input?
 Your puzzle input is 29000000.
 */
 
-use std::io::{self, BufRead};
 use std::collections::HashSet;
+use advent_core::{parse_number, Answer, ParseError, Solution};
 
 fn divisors(house: i64) -> Vec<i64> {
     let mut divs: HashSet<i64> = HashSet::new();
@@ -60,7 +60,19 @@ fn presents_to_house(house: i64) -> i64 {
     divisors(house).iter().fold(0, |sum, val| sum + val * 10)
 }
 
-fn min_house_to_get(count: i64) -> (i64, i64) {
+// After the elves decide to stop after 50 houses each, and deliver 11 presents
+// per house instead to make up for it
+fn lazy_presents_to_house(house: i64) -> i64 {
+    divisors(house)
+        .iter()
+        .filter(|&&elf| house / elf <= 50)
+        .fold(0, |sum, val| sum + val * 11)
+}
+
+fn min_house_to_get<F>(count: i64, presents_to_house: F) -> (i64, i64)
+where
+    F: Fn(i64) -> i64,
+{
     for h in 1.. {
         let presents = presents_to_house(h);
         if presents >= count {
@@ -72,23 +84,23 @@ fn min_house_to_get(count: i64) -> (i64, i64) {
     (0, 0)
 }
 
-pub fn problem() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-
-        match sline.parse::<i64>() {
-            Ok(count) => {
-                let (house, actual_count) = min_house_to_get(count);
-                println!(
-                    "Minimum house to get at least {} is {} and it gets {} presents",
-                    count, house, actual_count
-                );
-            }
-            Err(_) => {
-                println!("Invalid number: {}", sline);
-            }
-        }
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = i64;
+
+    fn parse(&self, input: &str) -> Result<i64, ParseError> {
+        parse_number(input.trim()).map_err(|e| e.at(1, 1))
+    }
+
+    fn part1(&self, input: &i64) -> Answer {
+        let (house, _) = min_house_to_get(*input, presents_to_house);
+        house.into()
+    }
+
+    fn part2(&self, input: &i64) -> Answer {
+        let (house, _) = min_house_to_get(*input, lazy_presents_to_house);
+        house.into()
     }
 }
 
@@ -102,3 +114,12 @@ fn test_given_elves_delivery() {
     assert_eq!(120, presents_to_house(6));
     assert_eq!(80, presents_to_house(7));
 }
+
+#[test]
+fn test_lazy_elves_delivery() {
+    assert_eq!(11, lazy_presents_to_house(1));
+    assert_eq!(77, lazy_presents_to_house(4));
+    // Elf 1 stopped after house 50, and elf 2 after house 100
+    assert_eq!((51 + 17 + 3) * 11, lazy_presents_to_house(51));
+    assert_eq!((4, 70), min_house_to_get(70, presents_to_house));
+}
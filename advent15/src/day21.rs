@@ -68,20 +68,17 @@ is the least amount of gold you can spend and still win the fight?
 */
 
 use std::fmt;
-use std::num::ParseIntError;
-use std::io::{self, BufRead};
-
-const DEBUG: bool = false;
+use advent_core::{parse_number, Answer, ParseError, Solution};
 
 #[derive(Debug, Clone, PartialEq)]
-enum ItemType {
+pub enum ItemType {
     Weapon,
     Armor,
     Ring,
 }
 
 #[derive(Clone)]
-struct Item {
+pub struct Item {
     name: String,
     cost: i32,
     damage: i32,
@@ -100,7 +97,7 @@ impl fmt::Debug for Item {
 }
 
 #[derive(Debug, Clone)]
-struct Player {
+pub struct Player {
     name: String,
     hit_points: i32,
     damage: i32,
@@ -135,9 +132,7 @@ impl Player {
             p.hit_points -= 1;
         }
 
-        if DEBUG {
-            println!("{} goes down to {} hit point", p.name, p.hit_points);
-        }
+        trace!("{} goes down to {} hit point", p.name, p.hit_points);
     }
 
     pub fn lost(&self) -> bool {
@@ -267,7 +262,6 @@ impl Game {
         name == self.player1.name
     }
 
-    #[allow(dead_code)]
     fn player2_wins(&self) -> bool {
         let (_, name) = self.play();
         name == self.player2.name
@@ -304,10 +298,9 @@ fn item_costs(items: &Vec<Item>) -> i32 {
     items.iter().fold(0, |sum, item| sum + item.cost)
 }
 
-// We need to compute valid combinations of items one can buy by the order of
-// cost, and then try to find the lowest amount we have to spend and still win.
-fn best_items_to_buy(boss: Player) {
-    let game = Game {
+// A game against the boss, with nothing bought from the store yet
+fn store_game(boss: Player) -> Game {
+    Game {
         player1: Player {
             name: "player".to_owned(),
             hit_points: 100,
@@ -316,8 +309,11 @@ fn best_items_to_buy(boss: Player) {
         },
         player2: boss,
         items: Game::store_items(),
-    };
+    }
+}
 
+// Valid combinations of items one can buy, cheapest first
+fn combinations_by_cost(game: &Game) -> Vec<Vec<Item>> {
     let mut cmbs = game.item_combinations();
     // We want to sort item combinations by their cost
     cmbs.sort_by(|a, b| {
@@ -325,44 +321,91 @@ fn best_items_to_buy(boss: Player) {
         let s2 = item_costs(b);
         s1.cmp(&s2)
     });
+    cmbs
+}
+
+// We need to compute valid combinations of items one can buy by the order of
+// cost, and then try to find the lowest amount we have to spend and still win.
+fn best_items_to_buy(boss: Player) -> Option<i32> {
+    let game = store_game(boss);
+    let cmbs = combinations_by_cost(&game);
 
     // Now we try to find an item combination which allows player1 to win the
     // game with minimum expenditure
-    let mut player1_won = false;
     for cmb in &cmbs {
         let mut g = game.clone();
         g.fortify(true, cmb);
 
         if g.player1_wins() {
-            println!("Player 1 wins by combination: {:?}", cmb);
-            let cost = item_costs(cmb);
-            println!("Cost of the combination: {}", cost);
-            player1_won = true;
-            break;
+            debug!("Player 1 wins by combination: {:?}", cmb);
+            return Some(item_costs(cmb));
+        }
+    }
+
+    // There are no items which allows player to win. Boss always wins!
+    None
+}
+
+// The shopkeeper is working with the boss: find the most we can be talked into
+// spending and still lose the fight.
+fn worst_items_to_buy(boss: Player) -> Option<i32> {
+    let game = store_game(boss);
+    let cmbs = combinations_by_cost(&game);
+
+    for cmb in cmbs.iter().rev() {
+        let mut g = game.clone();
+        g.fortify(true, cmb);
+
+        if g.player2_wins() {
+            debug!("Player 2 wins against combination: {:?}", cmb);
+            return Some(item_costs(cmb));
         }
     }
 
-    if !player1_won {
-        println!("There are no items which allows player to win. Boss always wins!");
+    // Whatever we buy, the player wins
+    None
+}
+
+// Parse the number following a prefix like "Damage: "
+fn parse_stat(s: &str, prefix: &str) -> Result<i32, ParseError> {
+    if !s.starts_with(prefix) {
+        return Err(ParseError::new(s, format!("{:?}", prefix)).at(0, 1));
     }
+
+    parse_number(s[prefix.len()..].trim()).map_err(|e| e.at(0, prefix.len() + 1))
+}
+
+pub fn parse_hit_points(s: &str) -> Result<i32, ParseError> {
+    parse_stat(s, "Hit Points: ")
 }
 
-pub fn parse_hit_points(s: &String) -> Result<i32, ParseIntError> {
-    let hs: String = s.chars().skip("Hit Points: ".len()).collect();
-    hs.parse::<i32>()
+pub fn parse_damage(s: &str) -> Result<i32, ParseError> {
+    parse_stat(s, "Damage: ")
 }
 
-pub fn parse_damage(s: &String) -> Result<i32, ParseIntError> {
-    let hs: String = s.chars().skip("Damage: ".len()).collect();
-    hs.parse::<i32>()
+fn parse_armor(s: &str) -> Result<i32, ParseError> {
+    parse_stat(s, "Armor: ")
 }
 
-fn parse_armor(s: &String) -> Result<i32, ParseIntError> {
-    let hs: String = s.chars().skip("Armor: ".len()).collect();
-    hs.parse::<i32>()
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Player;
+
+    fn parse(&self, input: &str) -> Result<Player, ParseError> {
+        parse_boss(input)
+    }
+
+    fn part1(&self, input: &Player) -> Answer {
+        best_items_to_buy(input.clone()).into()
+    }
+
+    fn part2(&self, input: &Player) -> Answer {
+        worst_items_to_buy(input.clone()).into()
+    }
 }
 
-pub fn problem() {
+fn parse_boss(input: &str) -> Result<Player, ParseError> {
     let mut boss = Player {
         name: format!("boss"),
         hit_points: 0,
@@ -374,47 +417,26 @@ pub fn problem() {
     let mut dm_found = false;
     let mut ar_found = false;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-
-        if sline.starts_with("Hit Points: ") {
-            match parse_hit_points(&sline) {
-                Ok(num) => {
-                    boss.hit_points = num;
-                    hp_found = true;
-                }
-                Err(_) => {
-                    println!("Invalid hit points: {}", sline);
-                }
-            }
-        } else if sline.starts_with("Damage: ") {
-            match parse_damage(&sline) {
-                Ok(num) => {
-                    boss.damage = num;
-                    dm_found = true;
-                }
-                Err(_) => {
-                    println!("Invalid hit points: {}", sline);
-                }
-            }
-        } else if sline.starts_with("Armor: ") {
-            match parse_armor(&sline) {
-                Ok(num) => {
-                    boss.armor = num;
-                    ar_found = true;
-                }
-                Err(_) => {
-                    println!("Invalid hit points: {}", sline);
-                }
-            }
+    for (n, line) in input.lines().enumerate() {
+        let on_line = |e: ParseError| e.on_line(n + 1);
+
+        if line.starts_with("Hit Points: ") {
+            boss.hit_points = parse_hit_points(line).map_err(on_line)?;
+            hp_found = true;
+        } else if line.starts_with("Damage: ") {
+            boss.damage = parse_damage(line).map_err(on_line)?;
+            dm_found = true;
+        } else if line.starts_with("Armor: ") {
+            boss.armor = parse_armor(line).map_err(on_line)?;
+            ar_found = true;
         }
     }
 
-    if hp_found && dm_found && ar_found {
-        best_items_to_buy(boss);
-    } else {
-        println!("Error: required information missing for boss");
+    match (hp_found, dm_found, ar_found) {
+        (false, _, _) => Err(ParseError::new("", "a \"Hit Points: \" line")),
+        (_, false, _) => Err(ParseError::new("", "a \"Damage: \" line")),
+        (_, _, false) => Err(ParseError::new("", "an \"Armor: \" line")),
+        _ => Ok(boss),
     }
 }
 
@@ -445,6 +467,23 @@ fn test_example_winner() {
     assert!(!game.player2_wins());
 }
 
+#[test]
+fn test_items_to_buy() {
+    let boss = |hit_points, damage| Player {
+        name: "boss".to_owned(),
+        hit_points,
+        damage,
+        armor: 0,
+    };
+
+    // The cheapest dagger beats a weak boss, and nothing loses against it
+    assert_eq!(Some(8), best_items_to_buy(boss(1, 1)));
+    assert_eq!(None, worst_items_to_buy(boss(1, 1)));
+    // Nothing beats an overwhelming boss, and so everything loses
+    assert_eq!(None, best_items_to_buy(boss(1000, 1000)));
+    assert_eq!(Some(74 + 102 + 100 + 80), worst_items_to_buy(boss(1000, 1000)));
+}
+
 #[test]
 fn test_parse_hit_points() {
     let pr = parse_hit_points(&"Hit Points: 34".to_owned());
@@ -462,3 +501,15 @@ fn test_parse_armor() {
     let pr = parse_armor(&"Armor: 48".to_owned());
     assert_eq!(pr, Ok(48));
 }
+
+#[test]
+fn test_parse_boss_errors() {
+    assert_eq!(
+        Some(ParseError::new("4x", "a number").at(2, 9)),
+        parse_boss("Hit Points: 100\nDamage: 4x\nArmor: 2").err()
+    );
+    assert_eq!(
+        Some(ParseError::new("", "an \"Armor: \" line")),
+        parse_boss("Hit Points: 100\nDamage: 8").err()
+    );
+}
@@ -135,14 +135,24 @@ Poison deals 3 damage. This kills the boss, and the player wins.
 You start with 50 hit points and 500 mana points. The boss's actual stats are in
 your puzzle input. What is the least amount of mana you can spend and still win
 the fight? (Do not include mana recharge effects as "spending" negative mana.)
+
+--- Part Two ---
+
+On the next run through the game, you increase the difficulty to hard.
+
+At the start of each player turn (before any other effects apply), you lose 1
+hit point. If this brings you to or below 0 hit points, you lose.
+
+With the same starting stats for you and the boss, what is the least amount of
+mana you can spend and still win the fight?
 */
-extern crate log;
-extern crate env_logger;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
 
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 use day21::{parse_damage, parse_hit_points};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Player {
     name: String,
     hit_points: i32,
@@ -159,26 +169,26 @@ impl Player {
     pub fn incur_damage(&mut self, attack: i32) {
         let d = attack - self.armor;
         if d < 1 {
-            debug!("incurring damage of 1 to {}", self.name);
+            trace!("incurring damage of 1 to {}", self.name);
             self.hit_points -= 1; // at least 1 point damage always
         } else {
-            debug!("incurring damage of {} to {}", d, self.name);
+            trace!("incurring damage of {} to {}", d, self.name);
             self.hit_points -= d;
         }
     }
 
     pub fn apply_healing(&mut self, healing: i32) {
-        debug!("applying healing of {} to {}", healing, self.name);
+        trace!("applying healing of {} to {}", healing, self.name);
         self.hit_points += healing;
     }
 
     pub fn apply_armor(&mut self, armor: i32) {
-        debug!("adding {} armor to {}", armor, self.name);
+        trace!("adding {} armor to {}", armor, self.name);
         self.armor += armor;
     }
 
     pub fn add_mana(&mut self, mana: i32) {
-        debug!("adding {} mana to {}", mana, self.name);
+        trace!("adding {} mana to {}", mana, self.name);
         self.mana += mana;
     }
 
@@ -188,182 +198,321 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct Spell {
-    name: String,
-    cost: i32,
-    damage: (i32, i32),
-    healing: (i32, i32),
-    armor: (i32, i32),
-    mana: (i32, i32),
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Spell {
+    MagicMissile,
+    Drain,
+    Shield,
+    Poison,
+    Recharge,
 }
 
-trait CastSpell {
-    fn active(&self) -> bool;
-    fn cast(&mut self, player: &mut Player, opponent: &mut Player) -> bool;
-}
+const SPELLS: [Spell; 5] = [
+    Spell::MagicMissile,
+    Spell::Drain,
+    Spell::Shield,
+    Spell::Poison,
+    Spell::Recharge,
+];
 
 impl Spell {
-    fn can_damage(&self) -> bool {
-        self.damage.1 > 0
-    }
-    fn can_heal(&self) -> bool {
-        self.healing.1 > 0
-    }
-    fn has_armor(&self) -> bool {
-        self.armor.1 > 0
-    }
-    fn has_mana(&self) -> bool {
-        self.mana.1 > 0
+    fn cost(self) -> i32 {
+        match self {
+            Spell::MagicMissile => 53,
+            Spell::Drain => 73,
+            Spell::Shield => 113,
+            Spell::Poison => 173,
+            Spell::Recharge => 229,
+        }
     }
 
-    fn use_damage(&mut self) -> i32 {
-        if self.can_damage() {
-            self.damage.1 -= 1;
-            return self.damage.0;
+    // Number of turns the effect started by the spell lasts, or 0 if it
+    // takes effect immediately
+    fn duration(self) -> i32 {
+        match self {
+            Spell::MagicMissile | Spell::Drain => 0,
+            Spell::Shield | Spell::Poison => 6,
+            Spell::Recharge => 5,
         }
-
-        0
     }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    PlayerWins,
+    BossWins,
+    Ongoing,
+}
+
+// The state of a fight between the wizard and the boss, at the start of one
+// of the wizard's turns
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Game {
+    player: Player,
+    boss: Player,
+    // Active effects and their remaining timers, in spell order
+    effects: Vec<(Spell, i32)>,
+    // In hard mode the player loses a hit point at the start of each turn
+    hard: bool,
+}
 
-    fn use_healing(&mut self) -> i32 {
-        if self.can_heal() {
-            self.healing.1 -= 1;
-            return self.healing.0;
+impl Game {
+    pub fn new(hit_points: i32, mana: i32, boss_hit_points: i32, boss_damage: i32) -> Game {
+        Game {
+            player: Player {
+                name: "player".to_owned(),
+                hit_points,
+                armor: 0,
+                damage: 0,
+                mana,
+            },
+            boss: Player {
+                name: "boss".to_owned(),
+                hit_points: boss_hit_points,
+                armor: 0,
+                damage: boss_damage,
+                mana: 0,
+            },
+            effects: vec![],
+            hard: false,
         }
+    }
 
-        0
+    pub fn hard(mut self) -> Game {
+        self.hard = true;
+        self
     }
 
-    fn use_armor(&mut self) -> i32 {
-        if self.has_armor() {
-            self.armor.1 -= 1;
-            return self.armor.0;
+    fn outcome(&self) -> Outcome {
+        if self.boss.lost() {
+            Outcome::PlayerWins
+        } else if self.player.lost() {
+            Outcome::BossWins
+        } else {
+            Outcome::Ongoing
         }
-
-        0
     }
 
-    fn use_mana(&mut self) -> i32 {
-        if self.has_mana() {
-            self.mana.1 -= 1;
-            return self.mana.0;
+    // Apply every active effect at the start of a turn, ending those whose
+    // timer runs out
+    fn apply_effects(&mut self) {
+        for &mut (spell, ref mut timer) in &mut self.effects {
+            match spell {
+                Spell::Poison => self.boss.incur_damage(3),
+                Spell::Recharge => self.player.add_mana(101),
+                _ => {}
+            }
+
+            *timer -= 1;
+            trace!("{:?}'s timer is now {}", spell, timer);
+
+            if *timer == 0 && spell == Spell::Shield {
+                self.player.apply_armor(-7);
+            }
         }
 
-        0
+        self.effects.retain(|&(_, timer)| timer > 0);
     }
-}
 
-impl CastSpell for Spell {
-    fn active(&self) -> bool {
-        // This if any one of Spell's effects are active, then it is still active
-        (self.damage.1 > 0) || (self.healing.1 > 0) || (self.armor.1 > 0) || (self.mana.1 > 0)
+    fn can_cast(&self, spell: Spell) -> bool {
+        spell.cost() <= self.player.mana && self.effects.iter().all(|&(s, _)| s != spell)
     }
 
-    fn cast(&mut self, player: &mut Player, opponent: &mut Player) -> bool {
-        if !self.active() {
-            debug!("spell {} is not active", self.name);
-            return false;
+    fn cast(&mut self, spell: Spell) {
+        debug!("{} casts {:?}", self.player.name, spell);
+        self.player.mana -= spell.cost();
+
+        match spell {
+            Spell::MagicMissile => self.boss.incur_damage(4),
+            Spell::Drain => {
+                self.boss.incur_damage(2);
+                self.player.apply_healing(2);
+            }
+            Spell::Shield => self.player.apply_armor(7),
+            Spell::Poison | Spell::Recharge => {}
+        }
+
+        if spell.duration() > 0 {
+            self.effects.push((spell, spell.duration()));
+            self.effects.sort();
         }
+    }
 
-        debug!("spell {} is active", self.name);
+    // Play the player's turn, casting the given spell, and then the boss's
+    // turn. Returns the game after both turns (or after the one which ended
+    // the fight) and the mana spent, or None if the spell can't be cast.
+    pub fn round(&self, spell: Spell) -> Option<(Game, i32)> {
+        let mut game = self.clone();
 
-        if self.can_damage() {
-            opponent.incur_damage(self.use_damage());
+        if game.hard {
+            game.player.hit_points -= 1;
+            if game.outcome() != Outcome::Ongoing {
+                return Some((game, 0));
+            }
         }
 
-        if self.can_heal() {
-            player.apply_healing(self.use_healing());
+        game.apply_effects();
+        if game.outcome() != Outcome::Ongoing {
+            return Some((game, 0));
         }
 
-        if self.has_armor() {
-            player.apply_armor(self.use_armor());
+        if !game.can_cast(spell) {
+            return None;
+        }
+        game.cast(spell);
+        if game.outcome() != Outcome::Ongoing {
+            return Some((game, spell.cost()));
         }
 
-        if self.has_mana() {
-            player.add_mana(self.use_mana());
+        game.apply_effects();
+        if game.outcome() == Outcome::Ongoing {
+            game.boss.attack(&mut game.player);
         }
 
-        true
+        Some((game, spell.cost()))
     }
-}
 
-#[derive(Debug, Clone, PartialEq)]
-struct Game {
-    player1: Player,
-    player2: Player,
+    // Play the given spells in turn, returning the game once they have all
+    // been cast or the fight is over
+    #[cfg(test)]
+    fn play(&self, spells: &[Spell]) -> Option<Game> {
+        let mut game = self.clone();
+        for &spell in spells {
+            if game.outcome() != Outcome::Ongoing {
+                break;
+            }
+            game = game.round(spell)?.0;
+        }
+        Some(game)
+    }
 }
 
-impl Game {
-    pub fn play(&self, spells: Vec<Spell>) -> String {
-        debug!("start of play player1: {:?}", self.player1);
-        debug!("start of play player2: {:?}", self.player2);
-        debug!("start spells: {:?}", spells);
-
-        let mut active_spells: Vec<Spell> = vec![];
-        let mut p1 = self.player1.clone();
-        let mut p2 = self.player2.clone();
-
-        if p2.lost() {
-            return p1.name;
-        }
-        if p1.lost() {
-            return p2.name;
+// The least mana the player can spend and still win, using Dijkstra's
+// algorithm over the game states reached after each round
+fn least_mana_to_win(game: &Game) -> Option<i32> {
+    let mut queue = BinaryHeap::new();
+    let mut seen: HashSet<Game> = HashSet::new();
+    queue.push(Reverse((0, game.clone())));
+
+    while let Some(Reverse((spent, game))) = queue.pop() {
+        match game.outcome() {
+            Outcome::PlayerWins => return Some(spent),
+            Outcome::BossWins => continue,
+            Outcome::Ongoing => {}
         }
 
-        loop {
-            // How to add to active spells?
-
-            // Cast all active spells
-            for s in &mut active_spells {
-                s.cast(&mut p1, &mut p2);
-            }
-
-            // Do p1 attacking
-            if p2.lost() {
-                return p1.name;
-            }
+        if !seen.insert(game.clone()) {
+            continue;
+        }
 
-            p2.attack(&mut p1);
-            // Do p2 attacking
-            if p1.lost() {
-                return p2.name;
+        for &spell in &SPELLS {
+            if let Some((next, cost)) = game.round(spell) {
+                queue.push(Reverse((spent + cost, next)));
             }
         }
     }
+
+    None
 }
 
-pub fn problem() {
-    env_logger::init();
+pub struct Day22;
 
-    let mut hit_points: Option<i32> = None;
-    let mut damage: Option<i32> = None;
+impl Solution for Day22 {
+    // Boss's hit points and damage
+    type Input = (i32, i32);
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+    fn parse(&self, input: &str) -> Result<(i32, i32), ParseError> {
+        let mut hit_points: Option<i32> = None;
+        let mut damage: Option<i32> = None;
 
-        if let Ok(num) = parse_hit_points(&sline) {
-            hit_points = Some(num);
+        for (n, line) in input.lines().enumerate() {
+            if line.starts_with("Hit Points: ") {
+                hit_points = Some(parse_hit_points(line).map_err(|e| e.on_line(n + 1))?);
+            } else if line.starts_with("Damage: ") {
+                damage = Some(parse_damage(line).map_err(|e| e.on_line(n + 1))?);
+            }
         }
-        if let Ok(num) = parse_damage(&sline) {
-            damage = Some(num);
+
+        match (hit_points, damage) {
+            (Some(hp), Some(dm)) => Ok((hp, dm)),
+            (None, _) => Err(ParseError::new("", "a \"Hit Points: \" line")),
+            (_, None) => Err(ParseError::new("", "a \"Damage: \" line")),
         }
     }
 
-    if hit_points.is_none() || damage.is_none() {
-        println!("Error: required hit points or damage points not found.");
-        return;
+    fn part1(&self, input: &(i32, i32)) -> Answer {
+        let &(hit_points, damage) = input;
+        least_mana_to_win(&Game::new(50, 500, hit_points, damage)).into()
+    }
+
+    fn part2(&self, input: &(i32, i32)) -> Answer {
+        let &(hit_points, damage) = input;
+        least_mana_to_win(&Game::new(50, 500, hit_points, damage).hard()).into()
     }
+}
+
+#[test]
+fn test_first_example() {
+    let game = Game::new(10, 250, 13, 8);
+
+    let (after, spent) = game.round(Spell::Poison).unwrap();
+    assert_eq!(
+        (2, 77, 10, 173),
+        (
+            after.player.hit_points,
+            after.player.mana,
+            after.boss.hit_points,
+            spent
+        )
+    );
 
-    println!(
-        "Hit points: {}, Damage points: {}",
-        hit_points.unwrap(),
-        damage.unwrap()
+    let end = game.play(&[Spell::Poison, Spell::MagicMissile]).unwrap();
+    assert_eq!(Outcome::PlayerWins, end.outcome());
+    assert_eq!(
+        (2, 0, 24),
+        (end.player.hit_points, end.player.armor, end.player.mana)
     );
+    assert_eq!(Some(226), least_mana_to_win(&game));
 }
 
 #[test]
-fn test_example() {
-    assert_eq!(10, 10);
+fn test_second_example() {
+    use self::Spell::*;
+
+    let game = Game::new(10, 250, 14, 8);
+    let spells = [Recharge, Shield, Drain, Poison, MagicMissile];
+
+    // Recharge can't be cast again while it is active
+    assert_eq!(None, game.play(&[Recharge, Recharge]));
+
+    let after_shield = game.play(&spells[..2]).unwrap();
+    assert_eq!(
+        (1, 7, 211),
+        (
+            after_shield.player.hit_points,
+            after_shield.player.armor,
+            after_shield.player.mana
+        )
+    );
+
+    let end = game.play(&spells).unwrap();
+    assert_eq!(Outcome::PlayerWins, end.outcome());
+    assert_eq!(
+        (1, 0, 114),
+        (end.player.hit_points, end.player.armor, end.player.mana)
+    );
+    assert_eq!(Some(641), least_mana_to_win(&game));
+}
+
+#[test]
+fn test_hard_mode() {
+    // Losing a hit point every turn, the player no longer survives the
+    // first example
+    let game = Game::new(10, 250, 13, 8).hard();
+    assert_eq!(
+        Outcome::BossWins,
+        game.play(&[Spell::Poison, Spell::MagicMissile])
+            .unwrap()
+            .outcome()
+    );
 }
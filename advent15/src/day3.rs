@@ -1,7 +1,8 @@
 // https://adventofcode.com/2015/day/3
-use std::io::{self, BufRead};
 use std::collections::HashSet;
 
+use advent_core::{Answer, ParseError, Solution};
+
 enum Direction {
     North,
     East,
@@ -31,29 +32,50 @@ fn process_move(current: Pos, dir: Direction, pos: &mut HashSet<Pos>) -> Pos {
 }
 
 fn process_moves(cmd: &String) -> i32 {
+    deliver(cmd, 1)
+}
+
+// Count the houses visited when the moves are taken in turn by the given
+// number of deliverers, all starting from the same house
+fn deliver(cmd: &str, deliverers: usize) -> i32 {
     let mut pos_set: HashSet<Pos> = HashSet::new();
-    let mut current = Pos { x: 0, y: 0 };
-    pos_set.insert(current);    // add initial position
+    let mut current = vec![Pos { x: 0, y: 0 }; deliverers];
+    pos_set.insert(current[0]);    // add initial position
 
-    for c in cmd.chars() {
+    for (i, c) in cmd.chars().enumerate() {
+        let who = i % deliverers;
         match c {
-            '^' => current = process_move(current, Direction::North, &mut pos_set),
-            '>' => current = process_move(current, Direction::East, &mut pos_set),
-            'v' => current = process_move(current, Direction::South, &mut pos_set),
-            '<' => current = process_move(current, Direction::West, &mut pos_set),
-            _ => println!("Invalid direction: {}", c),
+            '^' => current[who] = process_move(current[who], Direction::North, &mut pos_set),
+            '>' => current[who] = process_move(current[who], Direction::East, &mut pos_set),
+            'v' => current[who] = process_move(current[who], Direction::South, &mut pos_set),
+            '<' => current[who] = process_move(current[who], Direction::West, &mut pos_set),
+            _ => warn!("Invalid direction: {}", c),
         }
     }
 
     pos_set.len() as i32
 }
 
-pub fn problem() {
-    println!("2015, day 3");
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        let line = input.trim();
+        match line.chars().position(|c| !"^>v<".contains(c)) {
+            Some(i) => Err(ParseError::new(&line[i..i + 1], "one of ^, >, v or <").at(1, i + 1)),
+            None => Ok(line.to_owned()),
+        }
+    }
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        println!("Houses: {}", process_moves(&line.unwrap()));
+    fn part1(&self, input: &String) -> Answer {
+        process_moves(input).into()
+    }
+
+    // Santa and Robo-Santa take turns
+    fn part2(&self, input: &String) -> Answer {
+        deliver(input, 2).into()
     }
 }
 
@@ -62,4 +84,8 @@ fn test_examples() {
     assert_eq!(2, process_moves(&">".to_owned()));
     assert_eq!(4, process_moves(&"^>v<".to_owned()));
     assert_eq!(2, process_moves(&"^v^v^v^v^v".to_owned()));
+
+    assert_eq!(3, deliver("^v", 2));
+    assert_eq!(3, deliver("^>v<", 2));
+    assert_eq!(11, deliver("^v^v^v^v^v", 2));
 }
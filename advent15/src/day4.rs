@@ -3,15 +3,34 @@
 extern crate md5;
 
 use std;
-use std::io::{self, BufRead};
+
+use advent_core::{Answer, ParseError, Solution};
 
 fn calculate_match(s: &String) -> u64 {
+    find_hash(s, 5)
+}
+
+// Whether the hex form of the digest starts with the given number of zeros,
+// checked on the bytes so no string needs to be formatted
+fn has_leading_zeros(digest: &[u8], zeros: usize) -> bool {
+    (0..zeros).all(|i| {
+        let byte = digest[i / 2];
+        if i % 2 == 0 {
+            byte >> 4 == 0
+        } else {
+            byte & 0x0f == 0
+        }
+    })
+}
+
+// The lowest number which, appended to the key, gives an MD5 hash starting
+// with the given number of zeros
+fn find_hash(s: &str, zeros: usize) -> u64 {
     for num in 0..std::u64::MAX {
         let src: String = format!("{}{}", s, num);
         let digest = md5::compute(&src);
-        let hash: String = format!("{:x}", digest);
 
-        if hash.starts_with("00000") {
+        if has_leading_zeros(&digest.0, zeros) {
             return num;
         }
     }
@@ -19,13 +38,24 @@ fn calculate_match(s: &String) -> u64 {
     return 0;
 }
 
-pub fn problem() {
-    println!("2015, day 4");
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        match input.trim() {
+            "" => Err(ParseError::new("", "a secret key").on_line(1)),
+            key => Ok(key.to_owned()),
+        }
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        calculate_match(input).into()
+    }
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-        println!("{} -> {}", &sline, calculate_match(&sline));
+    fn part2(&self, input: &String) -> Answer {
+        find_hash(input, 6).into()
     }
 }
 
@@ -33,4 +63,6 @@ pub fn problem() {
 fn test_examples() {
     assert_eq!(609043, calculate_match(&"abcdef".to_owned()));
     assert_eq!(1048970, calculate_match(&"pqrstuv".to_owned()));
+    assert!(has_leading_zeros(&[0x00, 0x00, 0x0f], 5));
+    assert!(!has_leading_zeros(&[0x00, 0x00, 0x0f], 6));
 }
@@ -24,7 +24,7 @@ For example:
 How many strings are nice?
 */
 
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
 
 fn is_vowel(c: char) -> bool {
     match c {
@@ -68,19 +68,35 @@ fn is_nice(s: &String) -> bool {
     twice && (vowels >= 3)
 }
 
-pub fn problem() {
-    println!("2015, day 5");
+// The better model: a pair of letters appearing twice without overlapping, and
+// a letter which repeats with exactly one letter between
+fn is_nicer(s: &str) -> bool {
+    let cs: Vec<char> = s.chars().collect();
 
-    let mut count = 0;
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-        if is_nice(&sline) {
-            count += 1;
-        }
+    let pair_twice = (0..cs.len().saturating_sub(1)).any(|i| {
+        (i + 2..cs.len().saturating_sub(1)).any(|j| cs[i] == cs[j] && cs[i + 1] == cs[j + 1])
+    });
+    let repeat_between = cs.windows(3).any(|w| w[0] == w[2]);
+
+    pair_twice && repeat_between
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(|l| l.to_owned()).collect())
     }
 
-    println!("There are {} nice strings", count);
+    fn part1(&self, input: &Vec<String>) -> Answer {
+        input.iter().filter(|s| is_nice(s)).count().into()
+    }
+
+    fn part2(&self, input: &Vec<String>) -> Answer {
+        input.iter().filter(|s| is_nicer(s)).count().into()
+    }
 }
 
 #[test]
@@ -91,3 +107,12 @@ fn test_examples() {
     assert!(!is_nice(&"haegwjzuvuyypxyu".to_owned()));
     assert!(!is_nice(&"dvszwmarrgswjxmb".to_owned()));
 }
+
+#[test]
+fn test_nicer_examples() {
+    assert!(is_nicer("qjhvhtzxzqqjkmpb"));
+    assert!(is_nicer("xxyxx"));
+    assert!(!is_nicer("aaa"));
+    assert!(!is_nicer("uurcxstgmygtbstg"));
+    assert!(!is_nicer("ieodomkazucvgmuy"));
+}
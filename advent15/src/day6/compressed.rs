@@ -0,0 +1,211 @@
+// The lights grouped into the rectangles which every command either covers
+// completely or not at all. Only the edges of the commands' rectangles matter,
+// so the grid is cut at each of them: into columns of lights, and each column
+// into runs of rows. Each column is then a segment tree over its runs, where
+// switching a range of rows only touches the logarithmically many nodes
+// covering it, and the lights on are counted at its root.
+
+use super::{Command, Lights, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Switch {
+    Keep,
+    Set(bool),
+    Toggle,
+}
+
+impl Switch {
+    // The switch doing this and then the next
+    fn then(self, next: Switch) -> Switch {
+        match (self, next) {
+            (_, Switch::Keep) => self,
+            (_, Switch::Set(on)) => Switch::Set(on),
+            (Switch::Keep, Switch::Toggle) => Switch::Toggle,
+            (Switch::Set(on), Switch::Toggle) => Switch::Set(!on),
+            (Switch::Toggle, Switch::Toggle) => Switch::Keep,
+        }
+    }
+}
+
+// How many lights each node of a column's segment tree covers, which is the
+// same for every column
+fn node_sizes(runs: &[usize]) -> Vec<u32> {
+    fn build(sizes: &mut Vec<u32>, node: usize, runs: &[usize]) -> u32 {
+        sizes[node] = if runs.len() == 1 {
+            runs[0] as u32
+        } else {
+            let mid = runs.len() / 2;
+            build(sizes, 2 * node, &runs[..mid]) + build(sizes, 2 * node + 1, &runs[mid..])
+        };
+        sizes[node]
+    }
+
+    let mut sizes = vec![0; 4 * runs.len()];
+    if !runs.is_empty() {
+        build(&mut sizes, 1, runs);
+    }
+    sizes
+}
+
+// A column of lights as a segment tree over its runs of rows. Each node knows
+// how many of its lights are on, and holds back a switch which has yet to be
+// passed down to its children.
+#[derive(Debug, Clone)]
+struct Column {
+    on: Vec<u32>,
+    pending: Vec<Switch>,
+}
+
+impl Column {
+    fn new(nodes: usize) -> Column {
+        Column {
+            on: vec![0; nodes],
+            pending: vec![Switch::Keep; nodes],
+        }
+    }
+
+    fn switch_node(&mut self, sizes: &[u32], node: usize, switch: Switch) {
+        self.on[node] = match switch {
+            Switch::Keep => self.on[node],
+            Switch::Set(true) => sizes[node],
+            Switch::Set(false) => 0,
+            Switch::Toggle => sizes[node] - self.on[node],
+        };
+        self.pending[node] = self.pending[node].then(switch);
+    }
+
+    // Switch the runs from..to, of those lo..hi under the node
+    fn switch(
+        &mut self,
+        sizes: &[u32],
+        node: usize,
+        (lo, hi): (usize, usize),
+        (from, to): (usize, usize),
+        switch: Switch,
+    ) {
+        if to <= lo || hi <= from {
+            return;
+        }
+        if from <= lo && hi <= to {
+            self.switch_node(sizes, node, switch);
+            return;
+        }
+
+        let pending = self.pending[node];
+        self.switch_node(sizes, 2 * node, pending);
+        self.switch_node(sizes, 2 * node + 1, pending);
+        self.pending[node] = Switch::Keep;
+
+        let mid = lo + (hi - lo) / 2;
+        self.switch(sizes, 2 * node, (lo, mid), (from, to), switch);
+        self.switch(sizes, 2 * node + 1, (mid, hi), (from, to), switch);
+        self.on[node] = self.on[2 * node] + self.on[2 * node + 1];
+    }
+}
+
+/// The same lights as a `Board` which turns them on and off, but only as
+/// finely divided as the commands it was made for need. A command switches
+/// whole columns at once, and a range of rows in each in logarithmic time, so
+/// the work depends on how many edges the commands have rather than how many
+/// lights they cover.
+pub struct CompressedBoard {
+    // Where each column and each run of rows starts, and the end of the last
+    xs: Vec<i32>,
+    ys: Vec<i32>,
+    sizes: Vec<u32>,
+    columns: Vec<Column>,
+}
+
+// Where the edges of the rectangles fall along one side of the grid
+fn edges<F: Fn(&Pos) -> i32>(cmds: &[Command], coordinate: F) -> Vec<i32> {
+    let mut edges = vec![0];
+    for cmd in cmds {
+        let (from, to) = cmd.corners();
+        edges.push(coordinate(from));
+        edges.push(coordinate(to) + 1);
+    }
+
+    edges.sort();
+    edges.dedup();
+    edges
+}
+
+impl CompressedBoard {
+    /// A board with every light off, which can run the given commands.
+    pub fn new(cmds: &[Command]) -> CompressedBoard {
+        let xs = edges(cmds, |pos| pos.x);
+        let ys = edges(cmds, |pos| pos.y);
+        let runs: Vec<usize> = ys.windows(2).map(|w| (w[1] - w[0]) as usize).collect();
+        let sizes = node_sizes(&runs);
+
+        CompressedBoard {
+            columns: vec![Column::new(sizes.len()); xs.len() - 1],
+            xs,
+            ys,
+            sizes,
+        }
+    }
+}
+
+impl Lights for CompressedBoard {
+    // The command's corners have to be among those the board was made for
+    fn apply(&mut self, cmd: &Command) {
+        let (from, to) = cmd.corners();
+        let index = |edges: &[i32], edge: i32| {
+            edges
+                .binary_search(&edge)
+                .expect("a command the board wasn't made for")
+        };
+        let (x1, x2) = (index(&self.xs, from.x), index(&self.xs, to.x + 1));
+        let rows = (index(&self.ys, from.y), index(&self.ys, to.y + 1));
+
+        let switch = match *cmd {
+            Command::TurnOn { .. } => Switch::Set(true),
+            Command::TurnOff { .. } => Switch::Set(false),
+            Command::Toggle { .. } => Switch::Toggle,
+        };
+        let runs = self.ys.len() - 1;
+        for column in &mut self.columns[x1..x2] {
+            column.switch(&self.sizes, 1, (0, runs), rows, switch);
+        }
+    }
+
+    fn count_on(&self) -> usize {
+        self.columns
+            .iter()
+            .zip(self.xs.windows(2))
+            .map(|(column, w)| column.on[1] as usize * (w[1] - w[0]) as usize)
+            .sum()
+    }
+}
+
+#[test]
+fn test_same_as_board() {
+    use super::{parse_command, Board, Mode};
+
+    let cmds: Vec<Command> = [
+        "turn on 0,0 through 999,999",
+        "toggle 10,10 through 500,20",
+        "turn off 15,0 through 15,999",
+        "toggle 0,15 through 999,15",
+        "toggle 400,12 through 700,700",
+        "turn on 499,499 through 500,500",
+        "turn off 0,0 through 0,0",
+    ]
+    .iter()
+    .map(|cmd| parse_command(cmd).unwrap())
+    .collect();
+
+    let mut board = Board::fitting(&cmds, Mode::OnOff);
+    let mut compressed = CompressedBoard::new(&cmds);
+    for cmd in &cmds {
+        board.apply(cmd);
+        compressed.apply(cmd);
+        assert_eq!(board.count_on(), compressed.count_on());
+    }
+}
+
+#[test]
+fn test_no_commands() {
+    assert_eq!(0, CompressedBoard::new(&[]).count_on());
+}
@@ -0,0 +1,460 @@
+// https://adventofcode.com/2015/day/6
+
+/*
+--- Day 6: Probably a Fire Hazard ---
+
+Because your neighbors keep defeating you in the holiday house decorating
+contest year after year, you've decided to deploy one million lights in a
+1000x1000 grid.
+
+Furthermore, because you've been especially nice this year, Santa has mailed you
+instructions on how to display the ideal lighting configuration.
+
+Lights in your grid are numbered from 0 to 999 in each direction; the lights at
+each corner are at 0,0, 0,999, 999,999, and 999,0. The instructions include
+whether to turn on, turn off, or toggle various inclusive ranges given as
+coordinate pairs. Each coordinate pair represents opposite corners of a
+rectangle, inclusive; a coordinate pair like 0,0 through 2,2 therefore refers to
+9 lights in a 3x3 square. The lights all start turned off.
+
+To defeat your neighbors this year, all you have to do is set up your lights by
+doing the instructions Santa sent you in order.
+
+For example:
+- turn on 0,0 through 999,999 would turn on (or leave on) every light.
+- toggle 0,0 through 999,0 would toggle the first line of 1000 lights, turning
+  off the ones that were on, and turning on the ones that were off.
+- turn off 499,499 through 500,500 would turn off (or leave off) the middle four lights.
+
+After following the instructions, how many lights are lit?
+*/
+
+pub mod compressed;
+
+#[cfg(test)]
+use advent_core::exported;
+use advent_core::grammar::{after, coordinates, one_of, parse_line, Parser};
+use advent_core::{parse_lines, Answer, ExportError, ParseError, Setting, Solution};
+
+use self::compressed::CompressedBoard;
+
+/// A grid of lights which are switched a rectangle at a time.
+pub trait Lights {
+    fn apply(&mut self, cmd: &Command);
+    fn count_on(&self) -> usize;
+}
+
+/// What the commands do to each light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Lights are either on or off.
+    OnOff,
+    /// Turning a light on makes it 1 brighter, turning it off 1 dimmer down to
+    /// off, and toggling it 2 brighter.
+    Brightness,
+}
+
+/// Every light of the grid, each switched one by one.
+pub struct Board {
+    width: usize,
+    height: usize,
+    mode: Mode,
+    // Row by row, so each row of the rectangle is a single slice
+    lights: Vec<u32>,
+}
+
+impl Board {
+    /// A board with every light off.
+    pub fn new(width: usize, height: usize, mode: Mode) -> Board {
+        Board {
+            width,
+            height,
+            mode,
+            lights: vec![0; width * height],
+        }
+    }
+
+    /// A board just big enough for the commands, from 0,0 to the furthest
+    /// corner of any of them.
+    pub fn fitting(cmds: &[Command], mode: Mode) -> Board {
+        let (width, height) = extent(cmds);
+        Board::new(width, height, mode)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn brightness(&self, x: usize, y: usize) -> u32 {
+        self.lights[y * self.width + x]
+    }
+
+    pub fn total_brightness(&self) -> u64 {
+        self.lights.iter().map(|&light| u64::from(light)).sum()
+    }
+
+    /// The board as a binary greyscale PGM image, with the brightest light
+    /// white and lights which are off black. A PGM holds at most 65535, so
+    /// any brighter lights are shown as that.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let lights = self.lights.iter().map(|&light| light.min(65535));
+        let max = lights.clone().max().unwrap_or(0).max(1);
+        let mut image = format!("P5\n{} {}\n{}\n", self.width, self.height, max).into_bytes();
+
+        // Values over 255 take two bytes each, most significant first
+        for light in lights {
+            if max > 255 {
+                image.push((light >> 8) as u8);
+            }
+            image.push(light as u8);
+        }
+        image
+    }
+}
+
+impl Lights for Board {
+    fn apply(&mut self, cmd: &Command) {
+        let (from, to) = cmd.corners();
+        let switch: fn(u32) -> u32 = match (self.mode, cmd) {
+            (Mode::OnOff, &Command::TurnOn { .. }) => |_| 1,
+            (Mode::OnOff, &Command::TurnOff { .. }) => |_| 0,
+            (Mode::OnOff, &Command::Toggle { .. }) => |light| 1 - light,
+            (Mode::Brightness, &Command::TurnOn { .. }) => |light| light + 1,
+            (Mode::Brightness, &Command::TurnOff { .. }) => |light| light.saturating_sub(1),
+            (Mode::Brightness, &Command::Toggle { .. }) => |light| light + 2,
+        };
+
+        for y in from.y as usize..to.y as usize + 1 {
+            let row = y * self.width;
+            for light in &mut self.lights[row + from.x as usize..row + to.x as usize + 1] {
+                *light = switch(*light);
+            }
+        }
+    }
+
+    fn count_on(&self) -> usize {
+        self.lights.iter().filter(|&&light| light > 0).count()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    TurnOn { from: Pos, to: Pos },
+    TurnOff { from: Pos, to: Pos },
+    Toggle { from: Pos, to: Pos },
+}
+
+impl Command {
+    /// The first and last lights of the rectangle the command switches.
+    pub fn corners(&self) -> (&Pos, &Pos) {
+        match *self {
+            Command::TurnOn { ref from, ref to }
+            | Command::TurnOff { ref from, ref to }
+            | Command::Toggle { ref from, ref to } => (from, to),
+        }
+    }
+}
+
+// The width and height of the grid the commands need
+fn extent(cmds: &[Command]) -> (usize, usize) {
+    cmds.iter()
+        .map(|cmd| cmd.corners().1)
+        .fold((0, 0), |(width, height), to| {
+            (width.max(to.x as usize + 1), height.max(to.y as usize + 1))
+        })
+}
+
+/// Run the commands on the lights, and count how many are left on.
+pub fn count_lights<L: Lights>(mut lights: L, cmds: &[Command]) -> usize {
+    for cmd in cmds {
+        lights.apply(cmd);
+    }
+    lights.count_on()
+}
+
+/// Run the commands on a board, returning it as they leave it.
+pub fn run_board(cmds: &[Command], mode: Mode) -> Board {
+    let mut board = Board::fitting(cmds, mode);
+    for cmd in cmds {
+        board.apply(cmd);
+    }
+    board
+}
+
+/*
+toggle 461,550 through 564,900
+turn off 370,39 through 425,839
+turn on 599,989 through 806,993
+*/
+pub fn parse_command(cmd: &str) -> Result<Command, ParseError> {
+    let pos = || coordinates().map(|(x, y)| Pos { x, y });
+    let command = (one_of(&["turn on", "turn off", "toggle"]), pos(), after("through", pos()));
+    let (action, from, to) = parse_line(cmd, command)?;
+
+    // The grid starts at 0,0, and is as big as the commands need
+    if from.x < 0 || from.y < 0 || from.x > to.x || from.y > to.y {
+        return Err(ParseError::new(cmd.trim(), "a range like 0,0 through 999,999").within(cmd));
+    }
+
+    Ok(match action {
+        "turn on" => Command::TurnOn { from, to },
+        "turn off" => Command::TurnOff { from, to },
+        _ => Command::Toggle { from, to },
+    })
+}
+
+/// Which lights part one counts with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grid {
+    /// A `Board`, switching every light one by one.
+    Dense,
+    /// A `CompressedBoard`, switching ranges of lights.
+    Compressed,
+}
+
+/// The commands of a puzzle input, and the lights to run them on.
+#[derive(Debug, PartialEq)]
+pub struct Instructions {
+    pub cmds: Vec<Command>,
+    pub grid: Grid,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Instructions;
+
+    fn parse(&self, input: &str) -> Result<Instructions, ParseError> {
+        Ok(Instructions {
+            cmds: parse_lines(input, parse_command)?,
+            grid: Grid::Dense,
+        })
+    }
+
+    // Part one counts the lights with `grid=compressed` or `grid=dense`, the
+    // default. The other settings are the images `export` saves.
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Instructions, ParseError> {
+        let mut instructions = self.parse(input)?;
+
+        for setting in settings {
+            match setting.name.as_str() {
+                "grid" => {
+                    let grid = parse_line(&setting.value, one_of(&["dense", "compressed"]))?;
+                    instructions.grid = match grid {
+                        "compressed" => Grid::Compressed,
+                        _ => Grid::Dense,
+                    }
+                }
+                "lights" | "brightness" => {}
+                _ => {
+                    return Err(ParseError::new(
+                        setting.name.as_str(),
+                        "\"grid\", \"lights\" or \"brightness\"",
+                    ))
+                }
+            }
+        }
+
+        Ok(instructions)
+    }
+
+    // The lights can be saved as a PGM image once the commands have run, to a
+    // file or to standard output for "-": `lights` as part one leaves them,
+    // on or off, and `brightness` as part two does
+    fn export(&self, input: &Instructions, settings: &[Setting]) -> Result<(), ExportError> {
+        for setting in settings {
+            let mode = match setting.name.as_str() {
+                "lights" => Mode::OnOff,
+                "brightness" => Mode::Brightness,
+                _ => continue,
+            };
+            setting.write(&run_board(&input.cmds, mode).to_pgm())?;
+        }
+        Ok(())
+    }
+
+    // Switching every light one by one is still quicker on a grid the size of
+    // the puzzle's than the compressed board, so that is the default
+    fn part1(&self, input: &Instructions) -> Answer {
+        let cmds = &input.cmds;
+        match input.grid {
+            Grid::Dense => count_lights(Board::fitting(cmds, Mode::OnOff), cmds),
+            Grid::Compressed => count_lights(CompressedBoard::new(cmds), cmds),
+        }
+        .into()
+    }
+
+    fn part2(&self, input: &Instructions) -> Answer {
+        run_board(&input.cmds, Mode::Brightness).total_brightness().into()
+    }
+}
+
+#[test]
+fn test_parse_toggle() {
+    assert_eq!(
+        parse_command("toggle 461,550 through 564,900"),
+        Ok(Command::Toggle {
+            from: Pos { x: 461, y: 550 },
+            to: Pos { x: 564, y: 900 },
+        })
+    );
+
+    assert_eq!(
+        parse_command("toggle 0,0 through 1,34"),
+        Ok(Command::Toggle {
+            from: Pos { x: 0, y: 0 },
+            to: Pos { x: 1, y: 34 },
+        })
+    );
+}
+
+#[test]
+fn test_parse_turnon() {
+    assert_eq!(
+        parse_command("turn on 599,989 through 806,993"),
+        Ok(Command::TurnOn {
+            from: Pos { x: 599, y: 989 },
+            to: Pos { x: 806, y: 993 },
+        })
+    );
+
+    assert_eq!(
+        parse_command("turn on 0,0 through 23,34"),
+        Ok(Command::TurnOn {
+            from: Pos { x: 0, y: 0 },
+            to: Pos { x: 23, y: 34 },
+        })
+    );
+}
+
+#[test]
+fn test_parse_turnoff() {
+    assert_eq!(
+        parse_command("turn off 370,39 through 425,839"),
+        Ok(Command::TurnOff {
+            from: Pos { x: 370, y: 39 },
+            to: Pos { x: 425, y: 839 },
+        })
+    );
+
+    assert_eq!(
+        parse_command("turn off 370,39 through 425,839"),
+        Ok(Command::TurnOff {
+            from: Pos { x: 370, y: 39 },
+            to: Pos { x: 425, y: 839 },
+        })
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Err(ParseError::new("switch", "\"turn on\", \"turn off\" or \"toggle\"").at(0, 1)),
+        parse_command("switch 1,1 through 2,2")
+    );
+    assert_eq!(
+        Err(ParseError::new("to", "\"through\"").at(2, 13)),
+        Day6.parse("toggle 0,0 through 1,1\nturn on 1,1 to 2,2\n").map(|_| ())
+    );
+    assert_eq!(
+        Err(ParseError::new("toggle 5,0 through 1,1", "a range like 0,0 through 999,999").at(0, 1)),
+        parse_command("toggle 5,0 through 1,1")
+    );
+}
+
+#[test]
+fn test_brightness() {
+    let cmds = Day6
+        .parse("turn on 0,0 through 0,0\ntoggle 0,0 through 999,999\nturn off 0,0 through 1,0\n")
+        .unwrap()
+        .cmds;
+    assert_eq!(1 + 2_000_000 - 2, run_board(&cmds, Mode::Brightness).total_brightness());
+}
+
+#[test]
+fn test_count_lights() {
+    let cmds = Day6
+        .parse("turn on 0,0 through 999,999\ntoggle 0,0 through 999,0\nturn off 499,499 through 500,500\n")
+        .unwrap()
+        .cmds;
+
+    let board = Board::fitting(&cmds, Mode::OnOff);
+    assert_eq!(1_000_000 - 1000 - 4, count_lights(board, &cmds));
+    assert_eq!(1_000_000 - 1000 - 4, count_lights(CompressedBoard::new(&cmds), &cmds));
+}
+
+#[test]
+fn test_grid_size_and_image() {
+    let cmds = Day6
+        .parse("turn on 1,0 through 2,1\ntoggle 0,1 through 1,1\nturn off 2,0 through 2,0\n")
+        .unwrap()
+        .cmds;
+
+    let board = run_board(&cmds, Mode::OnOff);
+    assert_eq!((3, 2), (board.width(), board.height()));
+    assert_eq!(b"P5\n3 2\n1\n\x00\x01\x00\x01\x00\x01".to_vec(), board.to_pgm());
+
+    let board = run_board(&cmds, Mode::Brightness);
+    assert_eq!(3, board.brightness(1, 1));
+    assert_eq!(b"P5\n3 2\n3\n\x00\x01\x00\x02\x03\x01".to_vec(), board.to_pgm());
+}
+
+// The width, height, maximum and values of a binary PGM image
+#[cfg(test)]
+fn decode_pgm(image: &[u8]) -> (usize, usize, u32, Vec<u32>) {
+    let mut fields = image.splitn(5, |&c| c == b'\n' || c == b' ');
+    let mut field = || String::from_utf8(fields.next().unwrap().to_vec()).unwrap();
+    assert_eq!("P5", field());
+    let (width, height): (usize, usize) = (field().parse().unwrap(), field().parse().unwrap());
+    let max: u32 = field().parse().unwrap();
+
+    let bytes = if max > 255 { 2 } else { 1 };
+    let values = fields
+        .next()
+        .unwrap()
+        .chunks(bytes)
+        .map(|chunk| chunk.iter().fold(0, |value, &byte| value << 8 | u32::from(byte)))
+        .collect();
+    (width, height, max, values)
+}
+
+#[test]
+fn test_save_image() {
+    // Toggling a light 40000 times makes it brighter than a PGM can show
+    let mut input = "turn on 1,0 through 1,0\n".to_owned();
+    input += &"toggle 0,0 through 0,0\n".repeat(40_000);
+    let instructions = Day6.parse(&input).unwrap();
+
+    let image = exported("brightness", |settings| Day6.export(&instructions, settings));
+    assert_eq!((2, 1, 65535, vec![65535, 1]), decode_pgm(&image));
+    assert_eq!(
+        Err(ParseError::new("gif", "\"grid\", \"lights\" or \"brightness\"")),
+        Day6.parse_with(&input, &[Setting::new("gif", "-")])
+    );
+}
+
+#[test]
+fn test_grid_setting() {
+    let input = "turn on 0,0 through 999,999\ntoggle 0,0 through 999,0\n";
+    let grid = |value: &str| Day6.parse_with(input, &[Setting::new("grid", value)]);
+
+    let compressed = grid("compressed").unwrap();
+    assert_eq!(Grid::Compressed, compressed.grid);
+    assert_eq!(Answer::Number(999_000), Day6.part1(&compressed));
+    assert_eq!(Grid::Dense, grid("dense").unwrap().grid);
+    assert_eq!(Grid::Dense, Day6.parse(input).unwrap().grid);
+    assert_eq!(
+        Err(ParseError::new("sparse", "\"dense\" or \"compressed\"").at(0, 1)),
+        grid("sparse")
+    );
+}
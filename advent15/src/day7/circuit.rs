@@ -0,0 +1,481 @@
+// The booklet's operations wired up as a graph: every wire is driven by a
+// single gate, and the gates are sorted so each one comes after the gates
+// driving its inputs. The whole circuit is then evaluated in a single pass.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use super::word::Word;
+use super::{inputs, operate, Operand, Operation, Signals, Value, Wire};
+use advent_core::ParseError;
+
+/// Why a set of operations doesn't make a circuit which can be evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CircuitError {
+    /// Wires feeding a gate which no gate drives, and wires driven by more
+    /// than one gate, either of which may be empty.
+    Miswired {
+        undriven: Vec<Wire>,
+        driven_twice: Vec<Wire>,
+    },
+    /// Wires forming a loop, each driving the next and the last the first.
+    Cycle(Vec<Wire>),
+}
+
+impl CircuitError {
+    fn wires(&self) -> String {
+        match *self {
+            CircuitError::Miswired {
+                ref undriven,
+                ref driven_twice,
+            } => {
+                let wires: Vec<&str> = undriven
+                    .iter()
+                    .chain(driven_twice)
+                    .map(|wire| wire.as_str())
+                    .collect();
+                wires.join(", ")
+            }
+            CircuitError::Cycle(ref wires) => format!("{} -> {}", wires.join(" -> "), wires[0]),
+        }
+    }
+
+    fn expected(&self) -> &'static str {
+        match *self {
+            CircuitError::Miswired {
+                ref driven_twice, ..
+            } if driven_twice.is_empty() => "a gate driving every wire which is used",
+            CircuitError::Miswired { ref undriven, .. } if undriven.is_empty() => {
+                "a single gate driving each wire"
+            }
+            CircuitError::Miswired { .. } => "a single gate driving every wire which is used",
+            CircuitError::Cycle(_) => "a circuit without loops",
+        }
+    }
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CircuitError::Miswired {
+                ref undriven,
+                ref driven_twice,
+            } => {
+                let mut problems = vec![];
+                if !undriven.is_empty() {
+                    problems.push(format!("undriven wires: {}", undriven.join(", ")));
+                }
+                if !driven_twice.is_empty() {
+                    problems.push(format!(
+                        "wires driven more than once: {}",
+                        driven_twice.join(", ")
+                    ));
+                }
+                write!(f, "{}", problems.join("; "))
+            }
+            CircuitError::Cycle(_) => write!(f, "wires form a loop: {}", self.wires()),
+        }
+    }
+}
+
+impl From<CircuitError> for ParseError {
+    fn from(e: CircuitError) -> ParseError {
+        ParseError::new(e.wires(), e.expected())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit<V = Value> {
+    // Every gate comes after the gates driving its inputs
+    gates: Vec<Operation<V>>,
+    // Where the gate driving each wire is in `gates`
+    position: HashMap<Wire, usize>,
+    // The wires each wire feeds into
+    fanout: HashMap<Wire, Vec<Wire>>,
+}
+
+impl<V: Word> Circuit<V> {
+    pub fn new(ops: Vec<Operation<V>>) -> Result<Circuit<V>, CircuitError> {
+        let mut drivers: HashMap<&Wire, &Operation<V>> = HashMap::new();
+        let mut driven_twice: BTreeSet<Wire> = BTreeSet::new();
+        for op in &ops {
+            if drivers.insert(op.output(), op).is_some() {
+                driven_twice.insert(op.output().to_owned());
+            }
+        }
+        let undriven: BTreeSet<Wire> = ops
+            .iter()
+            .flat_map(inputs)
+            .filter(|wire| !drivers.contains_key(wire))
+            .cloned()
+            .collect();
+        if !undriven.is_empty() || !driven_twice.is_empty() {
+            return Err(CircuitError::Miswired {
+                undriven: undriven.into_iter().collect(),
+                driven_twice: driven_twice.into_iter().collect(),
+            });
+        }
+
+        // Sort the gates topologically: a gate is ready once all the gates
+        // driving its inputs are, starting with those only fed by values
+        let mut waiting: HashMap<&Wire, usize> = HashMap::new();
+        let mut feeds: HashMap<&Wire, Vec<&Wire>> = HashMap::new();
+        for op in &ops {
+            let ins = inputs(op);
+            waiting.insert(op.output(), ins.len());
+            for wire in ins {
+                feeds.entry(wire).or_default().push(op.output());
+            }
+        }
+
+        let mut ready: VecDeque<&Wire> = ops
+            .iter()
+            .map(Operation::output)
+            .filter(|wire| waiting[wire] == 0)
+            .collect();
+        let mut order: Vec<&Wire> = vec![];
+
+        while let Some(wire) = ready.pop_front() {
+            order.push(wire);
+            for &next in feeds.get(wire).into_iter().flatten() {
+                let count = waiting.get_mut(next).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() < ops.len() {
+            return Err(CircuitError::Cycle(find_cycle(&ops, &drivers, &order)));
+        }
+
+        let gates: Vec<Operation<V>> = order.iter().map(|wire| drivers[wire].clone()).collect();
+        let position = order
+            .iter()
+            .enumerate()
+            .map(|(i, &wire)| (wire.to_owned(), i))
+            .collect();
+        let fanout = feeds
+            .into_iter()
+            .map(|(wire, fed)| (wire.to_owned(), fed.into_iter().cloned().collect()))
+            .collect();
+
+        Ok(Circuit {
+            gates,
+            position,
+            fanout,
+        })
+    }
+
+    pub fn gates(&self) -> &[Operation<V>] {
+        &self.gates
+    }
+
+    /// The input wires, those driven by a gate fed only by values, which the
+    /// signal on a wire depends on. None if there is no such wire.
+    pub fn input_wires(&self, wire: &str) -> Option<Vec<Wire>> {
+        let mut stack: Vec<&Operation<V>> = vec![&self.gates[*self.position.get(wire)?]];
+        let mut seen: HashSet<&Wire> = HashSet::new();
+        let mut found: BTreeSet<Wire> = BTreeSet::new();
+
+        while let Some(gate) = stack.pop() {
+            let ins = inputs(gate);
+            if ins.is_empty() {
+                found.insert(gate.output().to_owned());
+            }
+
+            for input in ins {
+                if seen.insert(input) {
+                    stack.push(&self.gates[self.position[input]]);
+                }
+            }
+        }
+
+        Some(found.into_iter().collect())
+    }
+
+    // The gates fed by the wire, directly or not, in the order they have to
+    // be evaluated
+    fn downstream(&self, wire: &str) -> Vec<usize> {
+        let mut stack: Vec<&str> = vec![wire];
+        let mut cone: BTreeSet<usize> = BTreeSet::new();
+
+        while let Some(wire) = stack.pop() {
+            for fed in self.fanout.get(wire).into_iter().flatten() {
+                if cone.insert(self.position[fed]) {
+                    stack.push(fed);
+                }
+            }
+        }
+
+        cone.into_iter().collect()
+    }
+
+    /// The same circuit with every gate whose signal never changes reduced
+    /// to the value it drives, and the constants passed on to the gates they
+    /// feed, which are simplified where they can be.
+    pub fn fold_constants(&self) -> Circuit<V> {
+        let mut constants: HashMap<Wire, V> = HashMap::new();
+        let mut gates: Vec<Operation<V>> = vec![];
+
+        for gate in &self.gates {
+            let folded = gate.fold(&constants);
+            if let Operation::Set {
+                value: Operand::OpValue { value },
+                ref wire,
+            } = folded
+            {
+                constants.insert(wire.to_owned(), value);
+            }
+            gates.push(folded);
+        }
+
+        trace!(
+            "{} of {} gates folded to constants",
+            constants.len(),
+            gates.len()
+        );
+        // Folding only ever removes wires from a gate's inputs
+        Circuit::new(gates).expect("folding constants breaks the circuit")
+    }
+
+    pub fn evaluate(&self) -> Signals<V> {
+        let mut signals = Signals::new();
+        for gate in &self.gates {
+            // The inputs are always known by now, so every gate operates
+            operate(&mut signals, gate);
+        }
+
+        for (wire, value) in &signals {
+            trace!("{} -> {}", wire, value);
+        }
+        signals
+    }
+}
+
+/// The signals on a circuit, where any wire can be forced to a value instead
+/// of the one its gate drives. Changing a wire only evaluates the gates
+/// downstream of it again.
+pub struct Simulation<'a, V: 'a = Value> {
+    circuit: &'a Circuit<V>,
+    signals: Signals<V>,
+    overrides: HashMap<Wire, V>,
+}
+
+impl<'a, V: Word> Simulation<'a, V> {
+    pub fn new(circuit: &'a Circuit<V>) -> Simulation<'a, V> {
+        Simulation {
+            circuit,
+            signals: circuit.evaluate(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn signal(&self, wire: &str) -> Option<V> {
+        self.signals.get(wire).cloned()
+    }
+
+    /// Force the wire to a value, returning how many gates were evaluated
+    /// again, or None if there is no such wire.
+    pub fn override_wire(&mut self, wire: &str, value: V) -> Option<usize> {
+        if !self.circuit.position.contains_key(wire) {
+            return None;
+        }
+
+        self.overrides.insert(wire.to_owned(), value);
+        self.signals.insert(wire.to_owned(), value);
+        Some(self.propagate(wire))
+    }
+
+    /// Let the wire's own gate drive it again, returning how many gates were
+    /// evaluated again, or None if there is no such wire.
+    pub fn release(&mut self, wire: &str) -> Option<usize> {
+        let &position = self.circuit.position.get(wire)?;
+        if self.overrides.remove(wire).is_none() {
+            return Some(0);
+        }
+
+        operate(&mut self.signals, &self.circuit.gates[position]);
+        Some(1 + self.propagate(wire))
+    }
+
+    fn propagate(&mut self, wire: &str) -> usize {
+        let mut evaluated = 0;
+
+        for i in self.circuit.downstream(wire) {
+            let gate = &self.circuit.gates[i];
+            // Wires which are overridden themselves keep their value
+            if !self.overrides.contains_key(gate.output()) {
+                operate(&mut self.signals, gate);
+                evaluated += 1;
+            }
+        }
+
+        trace!(
+            "{} gates evaluated again after changing {}",
+            evaluated,
+            wire
+        );
+        evaluated
+    }
+}
+
+// Find a loop among the wires which couldn't be sorted. Each of them has an
+// input which couldn't be sorted either, so following those back from any of
+// them must eventually come round to a wire already seen.
+fn find_cycle<V: Word>(
+    ops: &[Operation<V>],
+    drivers: &HashMap<&Wire, &Operation<V>>,
+    sorted: &[&Wire],
+) -> Vec<Wire> {
+    let sorted: HashSet<&Wire> = sorted.iter().cloned().collect();
+    let unsorted = |wire: &&Wire| !sorted.contains(wire);
+
+    let mut path: Vec<&Wire> = vec![];
+    let mut seen: HashMap<&Wire, usize> = HashMap::new();
+    let mut wire = ops.iter().map(Operation::output).find(&unsorted).unwrap();
+
+    while !seen.contains_key(wire) {
+        seen.insert(wire, path.len());
+        path.push(wire);
+        wire = inputs(drivers[wire]).into_iter().find(&unsorted).unwrap();
+    }
+
+    // The path runs against the flow of the signals, so turn it around, and
+    // start from the first wire by name so the same loop is always reported
+    // the same way
+    let mut cycle: Vec<Wire> = path[seen[wire]..]
+        .iter()
+        .rev()
+        .map(|w| w.to_string())
+        .collect();
+    let first = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap_or(0);
+    cycle.rotate_left(first);
+    cycle
+}
+
+#[cfg(test)]
+fn circuit(ops: &[&str]) -> Result<Circuit, CircuitError> {
+    Circuit::new(
+        ops.iter()
+            .map(|op| super::parse_operation(op).unwrap())
+            .collect(),
+    )
+}
+
+#[test]
+fn test_gate_order() {
+    let circuit = circuit(&["e AND 1 -> f", "d -> e", "1 -> c", "c OR c -> d"]).unwrap();
+    let order: Vec<&Wire> = circuit.gates().iter().map(Operation::output).collect();
+
+    assert_eq!(vec!["c", "d", "e", "f"], order);
+    assert_eq!(Some(&1), circuit.evaluate().get("f"));
+}
+
+#[test]
+fn test_circuit_errors() {
+    let miswired = |undriven: &[&str], driven_twice: &[&str]| CircuitError::Miswired {
+        undriven: undriven.iter().map(|&wire| wire.to_owned()).collect(),
+        driven_twice: driven_twice.iter().map(|&wire| wire.to_owned()).collect(),
+    };
+    assert_eq!(
+        Some(miswired(&["x", "y"], &[])),
+        circuit(&["y AND x -> a", "x -> b"]).err()
+    );
+    assert_eq!(
+        Some(miswired(&[], &["b"])),
+        circuit(&["1 -> b", "2 -> b", "b -> a"]).err()
+    );
+
+    // Both problems are reported together rather than one at a time
+    let err = circuit(&["1 -> b", "x -> b", "b -> a"]).unwrap_err();
+    assert_eq!(miswired(&["x"], &["b"]), err);
+    assert_eq!(
+        "undriven wires: x; wires driven more than once: b",
+        err.to_string()
+    );
+
+    // Only the wires in the loop are reported, not those it feeds or is fed by
+    let err = circuit(&["1 -> x", "c AND x -> b", "b -> d", "d -> c", "c -> a"]).unwrap_err();
+    assert_eq!(
+        CircuitError::Cycle(vec!["b".to_owned(), "d".to_owned(), "c".to_owned()]),
+        err
+    );
+    assert_eq!("wires form a loop: b -> d -> c -> b", err.to_string());
+}
+
+#[test]
+fn test_override() {
+    let circuit = circuit(&[
+        "3 -> b",
+        "b LSHIFT 1 -> c",
+        "c OR 1 -> a",
+        "5 -> x",
+        "x -> y",
+    ])
+    .unwrap();
+    let mut sim = Simulation::new(&circuit);
+    assert_eq!(Some(7), sim.signal("a"));
+
+    // Only c and a are downstream of b
+    assert_eq!(Some(2), sim.override_wire("b", 6));
+    assert_eq!(Some(13), sim.signal("a"));
+    assert_eq!(Some(5), sim.signal("y"));
+
+    // An overridden wire keeps its value whatever happens upstream
+    assert_eq!(Some(1), sim.override_wire("c", 0));
+    assert_eq!(Some(1), sim.override_wire("b", 1));
+    assert_eq!(Some(1), sim.signal("a"));
+
+    assert_eq!(Some(2), sim.release("c"));
+    assert_eq!(Some(3), sim.signal("a"));
+    assert_eq!(None, sim.override_wire("z", 1));
+}
+
+#[test]
+fn test_input_wires() {
+    let circuit = circuit(&[
+        "1 -> p",
+        "2 -> q",
+        "p AND q -> r",
+        "r -> a",
+        "3 -> s",
+        "s OR a -> t",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        Some(vec!["p".to_owned(), "q".to_owned()]),
+        circuit.input_wires("a")
+    );
+    assert_eq!(
+        Some(vec!["p".to_owned(), "q".to_owned(), "s".to_owned()]),
+        circuit.input_wires("t")
+    );
+    assert_eq!(Some(vec!["s".to_owned()]), circuit.input_wires("s"));
+    assert_eq!(None, circuit.input_wires("z"));
+}
+
+#[test]
+fn test_fold_constants() {
+    let circuit = circuit(&[
+        "3 -> b",
+        "b LSHIFT 1 -> c",
+        "x AND c -> d",
+        "0 -> z",
+        "x OR z -> e",
+        "NOT b -> f",
+        "123 -> x",
+        "y -> x2",
+        "7 -> y",
+    ])
+    .unwrap();
+    let folded = circuit.fold_constants();
+    let netlist = super::export::to_netlist(&folded);
+
+    assert_eq!(circuit.evaluate(), folded.evaluate());
+    assert!(folded.gates().iter().all(|gate| inputs(gate).is_empty()));
+    assert!(netlist.contains("6 -> c\n"));
+    assert!(netlist.contains("65532 -> f\n"));
+}
@@ -0,0 +1,130 @@
+// Writing a circuit out for other tools: as a Graphviz graph to look at, or
+// as a netlist which can be diffed and read back in.
+//
+// The netlist is the booklet's own syntax, one gate per line and sorted by
+// the wire each gate drives, so two netlists of the same circuit are always
+// identical. Lines starting with '#' are comments.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::circuit::Circuit;
+use super::word::Word;
+use super::{inputs, parse_operation, Operand, Operation, Signals, Wire};
+use advent_core::{parse_lines, ParseError};
+
+// What a gate does, with any constant operands, e.g. "LSHIFT 2"
+fn gate_label<V: Word>(op: &Operation<V>) -> String {
+    let mut label = op.name().to_owned();
+    for operand in op.operands() {
+        if let Operand::OpValue { value } = *operand {
+            label.push_str(&format!(" {}", value));
+        }
+    }
+    label
+}
+
+fn wire_label<V: Word>(wire: &Wire, signals: Option<&Signals<V>>) -> String {
+    match signals.and_then(|signals| signals.get(wire)) {
+        Some(value) => format!("{} = {}", wire, value),
+        None => wire.to_owned(),
+    }
+}
+
+/// The circuit as a Graphviz graph: a node for each gate, named after the
+/// wire it drives, and an edge for each wire. Wires which don't feed any gate
+/// end in a point of their own. With signals, the wires are labelled with
+/// their values too.
+pub fn to_dot<V: Word>(circuit: &Circuit<V>, signals: Option<&Signals<V>>) -> String {
+    let used: HashSet<&Wire> = circuit.gates().iter().flat_map(inputs).collect();
+    let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+
+    for gate in circuit.gates() {
+        let wire = gate.output();
+        writeln!(
+            dot,
+            "    {:?} [shape=box, label={:?}];",
+            wire,
+            gate_label(gate)
+        )
+        .unwrap();
+
+        for input in inputs(gate) {
+            let label = wire_label(input, signals);
+            writeln!(dot, "    {:?} -> {:?} [label={:?}];", input, wire, label).unwrap();
+        }
+
+        if !used.contains(wire) {
+            let end = format!("{}:out", wire);
+            let label = wire_label(wire, signals);
+            writeln!(dot, "    {:?} [shape=point];", end).unwrap();
+            writeln!(dot, "    {:?} -> {:?} [label={:?}];", wire, end, label).unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// The circuit as a netlist, which `from_netlist` reads back in.
+pub fn to_netlist<V: Word>(circuit: &Circuit<V>) -> String {
+    let mut gates: Vec<&Operation<V>> = circuit.gates().iter().collect();
+    gates.sort_by(|a, b| a.output().cmp(b.output()));
+
+    let mut netlist = format!("# {} gates\n", gates.len());
+    for gate in gates {
+        writeln!(netlist, "{}", gate).unwrap();
+    }
+    netlist
+}
+
+pub fn from_netlist<V: Word>(netlist: &str) -> Result<Circuit<V>, ParseError> {
+    let ops = parse_lines(netlist, |line| {
+        if line.trim_start().starts_with('#') {
+            Ok(None)
+        } else {
+            parse_operation(line).map(Some)
+        }
+    })?;
+
+    Ok(Circuit::new(ops.into_iter().flatten().collect())?)
+}
+
+#[test]
+fn test_netlist_round_trip() {
+    let netlist = "# 4 gates\nb LSHIFT 1 -> a\n3 -> b\n\n# unused\nNOT b -> c\nb AND c -> d\n";
+    let circuit: Circuit = from_netlist(netlist).unwrap();
+
+    assert_eq!(
+        "# 4 gates\nb LSHIFT 1 -> a\n3 -> b\nNOT b -> c\nb AND c -> d\n",
+        to_netlist(&circuit)
+    );
+    assert_eq!(
+        to_netlist(&circuit),
+        to_netlist(&from_netlist::<u16>(&to_netlist(&circuit)).unwrap())
+    );
+    assert_eq!(
+        Some(ParseError::new(
+            "b",
+            "a gate driving every wire which is used"
+        )),
+        from_netlist::<u16>("b -> a").err()
+    );
+}
+
+#[test]
+fn test_dot() {
+    let circuit: Circuit = from_netlist("3 -> b\nb LSHIFT 1 -> a").unwrap();
+    let signals = circuit.evaluate();
+
+    assert_eq!(
+        "digraph circuit {\n    rankdir=LR;\n    \
+         \"b\" [shape=box, label=\"SET 3\"];\n    \
+         \"a\" [shape=box, label=\"LSHIFT 1\"];\n    \
+         \"b\" -> \"a\" [label=\"b = 3\"];\n    \
+         \"a:out\" [shape=point];\n    \
+         \"a\" -> \"a:out\" [label=\"a = 6\"];\n}\n",
+        to_dot(&circuit, Some(&signals))
+    );
+    assert!(to_dot(&circuit, None).contains("\"b\" -> \"a\" [label=\"b\"];"));
+}
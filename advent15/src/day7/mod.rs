@@ -0,0 +1,741 @@
+// https://adventofcode.com/2015/day/7
+/*
+--- Day 7: Some Assembly Required ---
+
+This year, Santa brought little Bobby Tables a set of wires and bitwise logic
+gates! Unfortunately, little Bobby is a little under the recommended age range,
+and he needs help assembling the circuit.
+
+Each wire has an identifier (some lowercase letters) and can carry a 16-bit
+signal (a number from 0 to 65535). A signal is provided to each wire by a gate,
+another wire, or some specific value. Each wire can only get a signal from one
+source, but can provide its signal to multiple destinations. A gate provides no
+signal until all of its inputs have a signal.
+
+The included instructions booklet describes how to connect the parts together: x
+AND y -> z means to connect wires x and y to an AND gate, and then connect its
+output to wire z.
+
+For example:
+
+- 123 -> x means that the signal 123 is provided to wire x.
+- x AND y -> z means that the bitwise AND of wire x and wire y is provided to wire z.
+- p LSHIFT 2 -> q means that the value from wire p is left-shifted by 2 and then
+  provided to wire q.
+- NOT e -> f means that the bitwise complement of the value from wire e is
+  provided to wire f.
+
+Other possible gates include OR (bitwise OR) and RSHIFT (right-shift). If, for
+some reason, you'd like to emulate the circuit instead, almost all programming
+languages (for example, C, JavaScript, or Python) provide operators for these
+gates.
+
+For example, here is a simple circuit:
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+
+After it is run, these are the signals on the wires:
+d: 72
+e: 507
+f: 492
+g: 114
+h: 65412
+i: 65079
+x: 123
+y: 456
+
+In little Bobby's kit's instructions booklet (provided as your puzzle input),
+what signal is ultimately provided to wire a?
+*/
+
+pub mod circuit;
+pub mod export;
+pub mod synthesis;
+pub mod word;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use self::circuit::{Circuit, Simulation};
+use self::export::{to_dot, to_netlist};
+use self::word::Word;
+#[cfg(test)]
+use advent_core::exported;
+use advent_core::grammar::{after, name, number, one_of, parse_line, Parser};
+use advent_core::{parse_lines, Answer, ExportError, ParseError, Setting, Solution};
+
+pub type Wire = String;
+// The booklet's signals are 16 bits wide
+pub type Value = u16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand<V = Value> {
+    OpWire { name: Wire },
+    OpValue { value: V },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation<V = Value> {
+    Set {
+        value: Operand<V>,
+        wire: Wire,
+    },
+    Not {
+        value: Operand<V>,
+        wire: Wire,
+    },
+    And {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Or {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Xor {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Nand {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Nor {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Add {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    Sub {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    LShift {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+    RShift {
+        left: Operand<V>,
+        right: Operand<V>,
+        wire: Wire,
+    },
+}
+
+impl<V: Word> Operand<V> {
+    pub fn constant(&self) -> Option<V> {
+        match *self {
+            Operand::OpValue { value } => Some(value),
+            Operand::OpWire { .. } => None,
+        }
+    }
+}
+
+impl<V: Word> Operation<V> {
+    // A gate with two operands from its keyword, one of GATES
+    fn binary(gate: &str, left: Operand<V>, right: Operand<V>, wire: Wire) -> Operation<V> {
+        match gate {
+            "AND" => Operation::And { left, right, wire },
+            "OR" => Operation::Or { left, right, wire },
+            "XOR" => Operation::Xor { left, right, wire },
+            "NAND" => Operation::Nand { left, right, wire },
+            "NOR" => Operation::Nor { left, right, wire },
+            "ADD" => Operation::Add { left, right, wire },
+            "SUB" => Operation::Sub { left, right, wire },
+            "LSHIFT" => Operation::LShift { left, right, wire },
+            "RSHIFT" => Operation::RShift { left, right, wire },
+            _ => panic!("{} is not a gate with two operands", gate),
+        }
+    }
+
+    /// The gate's keyword, as written in the booklet.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Operation::Set { .. } => "SET",
+            Operation::Not { .. } => "NOT",
+            Operation::And { .. } => "AND",
+            Operation::Or { .. } => "OR",
+            Operation::Xor { .. } => "XOR",
+            Operation::Nand { .. } => "NAND",
+            Operation::Nor { .. } => "NOR",
+            Operation::Add { .. } => "ADD",
+            Operation::Sub { .. } => "SUB",
+            Operation::LShift { .. } => "LSHIFT",
+            Operation::RShift { .. } => "RSHIFT",
+        }
+    }
+
+    pub fn operands(&self) -> Vec<&Operand<V>> {
+        match *self {
+            Operation::Set { ref value, .. } | Operation::Not { ref value, .. } => vec![value],
+            Operation::And {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Or {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Xor {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Nand {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Nor {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Add {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::Sub {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::LShift {
+                ref left,
+                ref right,
+                ..
+            }
+            | Operation::RShift {
+                ref left,
+                ref right,
+                ..
+            } => vec![left, right],
+        }
+    }
+
+    /// The wire the gate drives.
+    pub fn output(&self) -> &Wire {
+        match *self {
+            Operation::Set { ref wire, .. }
+            | Operation::Not { ref wire, .. }
+            | Operation::And { ref wire, .. }
+            | Operation::Or { ref wire, .. }
+            | Operation::Xor { ref wire, .. }
+            | Operation::Nand { ref wire, .. }
+            | Operation::Nor { ref wire, .. }
+            | Operation::Add { ref wire, .. }
+            | Operation::Sub { ref wire, .. }
+            | Operation::LShift { ref wire, .. }
+            | Operation::RShift { ref wire, .. } => wire,
+        }
+    }
+
+    // The same gate driving the same wire, with other operands
+    fn with_operands(&self, mut operands: Vec<Operand<V>>) -> Operation<V> {
+        let wire = self.output().to_owned();
+        let value = operands.remove(0);
+
+        match *self {
+            Operation::Set { .. } => Operation::Set { value, wire },
+            Operation::Not { .. } => Operation::Not { value, wire },
+            _ => Operation::binary(self.name(), value, operands.remove(0), wire),
+        }
+    }
+
+    /// The signal the gate drives, given the signals on its operands.
+    pub fn compute(&self, values: &[V]) -> V {
+        match *self {
+            Operation::Set { .. } => values[0],
+            Operation::Not { .. } => !values[0],
+            Operation::And { .. } => values[0] & values[1],
+            Operation::Or { .. } => values[0] | values[1],
+            Operation::Xor { .. } => values[0] ^ values[1],
+            Operation::Nand { .. } => !(values[0] & values[1]),
+            Operation::Nor { .. } => !(values[0] | values[1]),
+            Operation::Add { .. } => values[0].wrapping_add(values[1]),
+            Operation::Sub { .. } => values[0].wrapping_sub(values[1]),
+            Operation::LShift { .. } => values[0].shift_left(values[1]),
+            Operation::RShift { .. } => values[0].shift_right(values[1]),
+        }
+    }
+
+    /// The gate with wires of known constant signals replaced by their
+    /// values, and then simplified as far as the constants allow: to a single
+    /// value if every operand is one, or to one of the operands where the
+    /// other makes no difference, like `x OR 0 -> y`.
+    pub fn fold(&self, constants: &HashMap<Wire, V>) -> Operation<V> {
+        let operands: Vec<Operand<V>> = self
+            .operands()
+            .into_iter()
+            .map(|operand| match *operand {
+                Operand::OpWire { ref name } if constants.contains_key(name) => {
+                    Operand::OpValue {
+                        value: constants[name],
+                    }
+                }
+                _ => operand.clone(),
+            })
+            .collect();
+        let wire = self.output().to_owned();
+
+        let values: Option<Vec<V>> = operands.iter().map(|operand| operand.constant()).collect();
+        if let Some(values) = values {
+            let value = Operand::OpValue {
+                value: self.compute(&values),
+            };
+            return Operation::Set { value, wire };
+        }
+
+        if operands.len() < 2 {
+            return self.with_operands(operands);
+        }
+
+        let zero = V::ZERO;
+        let ones = !V::ZERO;
+        let (left, right) = (operands[0].constant(), operands[1].constant());
+        let is = |constant: Option<V>, value: V| constant == Some(value);
+        let set = |value: V| Some(Operand::OpValue { value });
+
+        let simplified = match self.name() {
+            "AND" if is(left, zero) || is(right, zero) => set(zero),
+            "OR" if is(left, ones) || is(right, ones) => set(ones),
+            "NAND" if is(left, zero) || is(right, zero) => set(ones),
+            "NOR" if is(left, ones) || is(right, ones) => set(zero),
+            "LSHIFT" | "RSHIFT" if is(left, zero) => set(zero),
+            "AND" if is(left, ones) => Some(operands[1].clone()),
+            "OR" | "XOR" | "ADD" if is(left, zero) => Some(operands[1].clone()),
+            "AND" if is(right, ones) => Some(operands[0].clone()),
+            "OR" | "XOR" | "ADD" | "SUB" | "LSHIFT" | "RSHIFT" if is(right, zero) => {
+                Some(operands[0].clone())
+            }
+            _ => None,
+        };
+
+        match simplified {
+            Some(value) => Operation::Set { value, wire },
+            None => self.with_operands(operands),
+        }
+    }
+}
+
+// Operations are written the way the booklet does, so they read back in
+impl<V: Word> fmt::Display for Operand<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::OpWire { ref name } => write!(f, "{}", name),
+            Operand::OpValue { value } => write!(f, "{}", value),
+        }
+    }
+}
+
+impl<V: Word> fmt::Display for Operation<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operands = self.operands();
+
+        match *self {
+            Operation::Set { .. } => write!(f, "{} -> {}", operands[0], self.output()),
+            Operation::Not { .. } => write!(f, "NOT {} -> {}", operands[0], self.output()),
+            _ => write!(
+                f,
+                "{} {} {} -> {}",
+                operands[0],
+                self.name(),
+                operands[1],
+                self.output()
+            ),
+        }
+    }
+}
+
+// The signal on each wire
+pub type Signals<V = Value> = HashMap<Wire, V>;
+
+// Drive the operation's wire, if the signals on all its operands are known
+fn operate<V: Word>(signals: &mut Signals<V>, op: &Operation<V>) -> bool {
+    let values: Option<Vec<V>> = op
+        .operands()
+        .into_iter()
+        .map(|operand| operand_value(signals, operand))
+        .collect();
+
+    match values {
+        Some(values) => {
+            signals.insert(op.output().clone(), op.compute(&values));
+            true
+        }
+        None => false,
+    }
+}
+
+fn operand_value<V: Word>(signals: &Signals<V>, op: &Operand<V>) -> Option<V> {
+    match *op {
+        Operand::OpWire { ref name } => signals.get(name).cloned(),
+        // Here we are setting the wire to a simple value
+        Operand::OpValue { value } => Some(value),
+    }
+}
+
+// The keywords of the gates with two operands
+const GATES: &[&str] = &["AND", "OR", "XOR", "NAND", "NOR", "ADD", "SUB", "LSHIFT", "RSHIFT"];
+
+// A number which fits in the width of the circuit's words, or a wire's name
+fn operand<V: Word>() -> impl Parser<Operand<V>> {
+    number()
+        .map(|value| Operand::OpValue { value })
+        .expecting(format!("a number of at most {} bits", V::BITS))
+        .or(name().map(|name| Operand::OpWire { name }))
+}
+
+pub fn parse_operation<V: Word>(s: &str) -> Result<Operation<V>, ParseError> {
+    let wire = || after("->", name());
+    let binary = (operand(), one_of(GATES), operand(), wire())
+        .map(|(left, gate, right, wire)| Operation::binary(gate, left, right, wire));
+    let set = (operand(), wire()).map(|(value, wire)| Operation::Set { value, wire });
+    let not = (after("NOT", operand()), wire()).map(|(value, wire)| Operation::Not { value, wire });
+
+    parse_line(s, binary.or(set).or(not))
+}
+
+// The wires feeding an operation, in order
+fn inputs<V: Word>(op: &Operation<V>) -> Vec<&Wire> {
+    op.operands()
+        .into_iter()
+        .filter_map(|operand| match *operand {
+            Operand::OpWire { ref name } => Some(name),
+            Operand::OpValue { .. } => None,
+        })
+        .collect()
+}
+
+// A search for the inputs on the free wires which put a value on the target
+// wire
+#[derive(Debug, Clone, PartialEq)]
+struct Search {
+    target: (Wire, Value),
+    free: Vec<Wire>,
+}
+
+impl Search {
+    fn new(
+        circuit: &Circuit,
+        target: (Wire, Value),
+        free: Vec<Wire>,
+    ) -> Result<Search, ParseError> {
+        if free.is_empty() {
+            return Err(ParseError::new(
+                "target",
+                "free wires to go with the target",
+            ));
+        }
+        let missing = Some(&target.0)
+            .into_iter()
+            .chain(&free)
+            .find(|&wire| !circuit.gates().iter().any(|gate| gate.output() == wire));
+        if let Some(missing) = missing {
+            return Err(ParseError::new(missing.as_str(), "a wire in the circuit"));
+        }
+
+        Ok(Search { target, free })
+    }
+
+    // Every input on the free wires which puts the value on the target wire,
+    // each written like "b=3 c=4"
+    fn found_inputs(&self, circuit: &Circuit) -> Vec<String> {
+        let (ref wire, value) = self.target;
+        let free: Vec<&str> = self.free.iter().map(|wire| wire.as_str()).collect();
+        let found = synthesis::solve(circuit, wire, value, &free).unwrap_or_default();
+        found
+            .iter()
+            .map(|values| {
+                let assigned: Vec<String> = free
+                    .iter()
+                    .zip(values)
+                    .map(|(wire, value)| format!("{}={}", wire, value))
+                    .collect();
+                assigned.join(" ")
+            })
+            .collect()
+    }
+}
+
+/// The circuit a booklet describes, along with the search for inputs its
+/// settings ask for, if any.
+#[derive(Debug, Clone)]
+pub struct Booklet {
+    pub circuit: Circuit,
+    search: Option<Search>,
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Booklet;
+
+    // The operations are wired up into a circuit straight away, so a booklet
+    // with loops or loose wires is rejected here rather than never resolving
+    fn parse(&self, input: &str) -> Result<Booklet, ParseError> {
+        let ops: Vec<Operation> = parse_lines(input, parse_operation)?;
+        for op in &ops {
+            trace!("Original op: {:?}", op);
+        }
+
+        Ok(Booklet {
+            circuit: Circuit::new(ops)?,
+            search: None,
+        })
+    }
+
+    // A `target` like "a:42" along with one or more `free` wires searches for
+    // the inputs on the free wires which give the target, for `export` to
+    // write to the file given by `found`. The `dot` and `netlist` settings
+    // are left for `export` too.
+    fn parse_with(&self, input: &str, settings: &[Setting]) -> Result<Booklet, ParseError> {
+        let mut booklet = self.parse(input)?;
+        let mut target: Option<(Wire, Value)> = None;
+        let mut free: Vec<Wire> = vec![];
+        let mut found = false;
+
+        for setting in settings {
+            match setting.name.as_str() {
+                "dot" | "netlist" => {}
+                "found" => found = true,
+                "target" => {
+                    target = Some(parse_line(&setting.value, (name(), after(":", number())))?)
+                }
+                "free" => free.push(parse_line(&setting.value, name())?),
+                _ => {
+                    return Err(ParseError::new(
+                        setting.name.as_str(),
+                        "\"dot\", \"netlist\", \"target\", \"free\" or \"found\"",
+                    ))
+                }
+            }
+        }
+
+        match target {
+            Some(_) if !found => {
+                return Err(ParseError::new(
+                    "target",
+                    "a \"found\" file to write the inputs to",
+                ))
+            }
+            Some(target) => booklet.search = Some(Search::new(&booklet.circuit, target, free)?),
+            None if found => return Err(ParseError::new("found", "a target to find inputs for")),
+            None if !free.is_empty() => {
+                return Err(ParseError::new("free", "a target for the free wires"))
+            }
+            None => {}
+        }
+
+        Ok(booklet)
+    }
+
+    // The circuit can be written out to a file, or to standard output for
+    // "-": `dot` as a Graphviz graph labelled with the signals, and `netlist`
+    // as a netlist. `found` writes the inputs found for a target, one set to
+    // a line.
+    fn export(&self, input: &Booklet, settings: &[Setting]) -> Result<(), ExportError> {
+        let circuit = &input.circuit;
+        for setting in settings {
+            match (setting.name.as_str(), input.search.as_ref()) {
+                ("dot", _) => {
+                    let signals = circuit.evaluate();
+                    setting.write(to_dot(circuit, Some(&signals)).as_bytes())?
+                }
+                ("netlist", _) => setting.write(to_netlist(circuit).as_bytes())?,
+                ("found", Some(search)) => {
+                    let lines: Vec<String> = search
+                        .found_inputs(circuit)
+                        .into_iter()
+                        .map(|found| found + "\n")
+                        .collect();
+                    setting.write(lines.concat().as_bytes())?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn part1(&self, input: &Booklet) -> Answer {
+        input.circuit.evaluate().get("a").cloned().into()
+    }
+
+    // Feed the signal on a back into b, and see what a becomes
+    fn part2(&self, input: &Booklet) -> Answer {
+        let mut sim = Simulation::new(&input.circuit);
+
+        sim.signal("a")
+            .and_then(|a| sim.override_wire("b", a))
+            .and_then(|_| sim.signal("a"))
+            .into()
+    }
+}
+
+#[cfg(test)]
+fn signals(ops: &[&str]) -> Signals {
+    let ops = ops.iter().map(|op| parse_operation(op).unwrap()).collect();
+    Circuit::new(ops).unwrap().evaluate()
+}
+
+#[test]
+fn test_display_operation() {
+    for op in &["123 -> x", "NOT y -> i", "x AND 1 -> d", "x LSHIFT 2 -> f", "y NOR z -> g"] {
+        assert_eq!(*op, parse_operation::<u16>(op).unwrap().to_string());
+    }
+}
+
+#[test]
+fn test_widths() {
+    let ops = [
+        "200 -> x",
+        "x ADD 100 -> a",
+        "x SUB 201 -> s",
+        "1 LSHIFT x -> l",
+        "x NAND 15 -> n",
+    ];
+
+    // The same booklet gives different signals on different widths
+    let parse8: Vec<Operation<u8>> = ops.iter().map(|op| parse_operation(op).unwrap()).collect();
+    let signals = Circuit::new(parse8).unwrap().evaluate();
+    assert_eq!(Some(&44), signals.get("a"));
+    assert_eq!(Some(&255), signals.get("s"));
+    assert_eq!(Some(&0), signals.get("l"));
+    assert_eq!(Some(&0xf7), signals.get("n"));
+
+    let parse32: Vec<Operation<u32>> = ops.iter().map(|op| parse_operation(op).unwrap()).collect();
+    let signals = Circuit::new(parse32).unwrap().evaluate();
+    assert_eq!(Some(&300), signals.get("a"));
+    assert_eq!(Some(&u32::MAX), signals.get("s"));
+    assert_eq!(Some(&0xffff_fff7), signals.get("n"));
+
+    assert_eq!(
+        Err(ParseError::new("300", "a number of at most 8 bits").at(0, 1)),
+        parse_operation::<u8>("300 XOR x -> y")
+    );
+}
+
+#[test]
+fn test_fold() {
+    let constants: HashMap<Wire, u16> = vec![("x".to_owned(), 3), ("z".to_owned(), 0)]
+        .into_iter()
+        .collect();
+    let fold = |op: &str| parse_operation::<u16>(op).unwrap().fold(&constants).to_string();
+
+    assert_eq!("6 -> y", fold("x LSHIFT 1 -> y"));
+    assert_eq!("0 -> y", fold("w AND z -> y"));
+    assert_eq!("w -> y", fold("w XOR z -> y"));
+    assert_eq!("65535 -> y", fold("w NAND 0 -> y"));
+    assert_eq!("w SUB 3 -> y", fold("w SUB x -> y"));
+    assert_eq!("NOT w -> y", fold("NOT w -> y"));
+}
+
+#[test]
+fn test_gates1() {
+    let signals = signals(&["x LSHIFT 2 -> f", "x -> y", "1 -> x"]);
+
+    assert_eq!(*signals.get("x").unwrap(), 1);
+    assert_eq!(*signals.get("f").unwrap(), 4);
+    assert_eq!(*signals.get("y").unwrap(), 1);
+}
+
+#[test]
+fn test_gates2() {
+    let signals = signals(&[
+        "123 -> x",
+        "456 -> y",
+        "x AND y -> d",
+        "x OR y -> e",
+        "x LSHIFT 2 -> f",
+        "y RSHIFT 2 -> g",
+        "NOT x -> h",
+        "NOT y -> i",
+    ]);
+
+    assert_eq!(*signals.get("f").unwrap(), 492);
+    assert_eq!(*signals.get("h").unwrap(), 65412);
+    assert_eq!(*signals.get("x").unwrap(), 123);
+    assert_eq!(*signals.get("d").unwrap(), 72);
+    assert_eq!(*signals.get("i").unwrap(), 65079);
+    assert_eq!(*signals.get("y").unwrap(), 456);
+    assert_eq!(*signals.get("g").unwrap(), 114);
+    assert_eq!(*signals.get("e").unwrap(), 507);
+}
+
+#[test]
+fn test_export_settings() {
+    let booklet = "123 -> x\nx AND 7 -> a\n";
+    let netlist = Setting::new("netlist", "-");
+    let booklet = Day7.parse_with(booklet, &[netlist]).unwrap();
+
+    let netlist = exported("netlist", |settings| Day7.export(&booklet, settings));
+    let netlist = String::from_utf8(netlist).unwrap();
+    assert_eq!(to_netlist(&booklet.circuit), netlist);
+    assert!(export::from_netlist::<Value>(&netlist).is_ok());
+
+    assert_eq!(
+        Err(ParseError::new(
+            "png",
+            "\"dot\", \"netlist\", \"target\", \"free\" or \"found\""
+        )),
+        Day7.parse_with("1 -> a", &[Setting::new("png", "-")])
+            .map(|_| ())
+    );
+}
+
+#[test]
+fn test_found_inputs() {
+    let booklet = "0 -> x\nx LSHIFT 1 -> d\nd XOR 5 -> a\n";
+    let search = |free: &str| {
+        let settings = [
+            Setting::new("target", "a:7"),
+            Setting::new("free", free),
+            Setting::new("found", "-"),
+        ];
+        Day7.parse_with(booklet, &settings)
+    };
+
+    let searched = search("x").unwrap();
+    let found = exported("found", |settings| Day7.export(&searched, settings));
+    assert_eq!("x=1\nx=32769\n", String::from_utf8(found).unwrap());
+    assert_eq!(
+        Err(ParseError::new("y", "a wire in the circuit")),
+        search("y").map(|_| ())
+    );
+
+    let errors = [
+        (
+            Setting::new("free", "a"),
+            ParseError::new("free", "a target for the free wires"),
+        ),
+        (
+            Setting::new("target", "a:1"),
+            ParseError::new("target", "a \"found\" file to write the inputs to"),
+        ),
+        (
+            Setting::new("found", "-"),
+            ParseError::new("found", "a target to find inputs for"),
+        ),
+    ];
+    for (setting, err) in errors.iter().cloned() {
+        assert_eq!(Err(err), Day7.parse_with("1 -> a", &[setting]).map(|_| ()));
+    }
+}
@@ -0,0 +1,314 @@
+// Working back from a signal to the inputs which give it. The free wires are
+// assigned a bit at a time, lowest first, and after each bit the circuit is
+// evaluated on just the bits known so far. As soon as any bit of the target
+// is known to be wrong, no assignment of the remaining bits can put it right,
+// so the search backs up and tries the other value of the last bit.
+
+use std::collections::{HashMap, HashSet};
+
+use super::circuit::Circuit;
+use super::word::Word;
+use super::{inputs, Operand, Operation};
+
+// A signal of which only some bits are known, the unknown ones being 0 in
+// `value`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Partial<V> {
+    known: V,
+    value: V,
+}
+
+impl<V: Word> Partial<V> {
+    fn exact(value: V) -> Partial<V> {
+        Partial {
+            known: !V::ZERO,
+            value,
+        }
+    }
+
+    fn is_exact(&self) -> bool {
+        self.known == !V::ZERO
+    }
+
+    fn zeros(self) -> V {
+        self.known & !self.value
+    }
+
+    fn not(self) -> Partial<V> {
+        Partial {
+            known: self.known,
+            value: !self.value & self.known,
+        }
+    }
+
+    fn and(self, other: Partial<V>) -> Partial<V> {
+        let ones = self.value & other.value;
+        Partial {
+            known: ones | self.zeros() | other.zeros(),
+            value: ones,
+        }
+    }
+
+    fn or(self, other: Partial<V>) -> Partial<V> {
+        let ones = self.value | other.value;
+        Partial {
+            known: ones | (self.zeros() & other.zeros()),
+            value: ones,
+        }
+    }
+}
+
+// What is known of a gate's signal from what is known of its operands
+fn compute<V: Word>(op: &Operation<V>, operands: &[Partial<V>]) -> Partial<V> {
+    if operands.iter().all(Partial::is_exact) {
+        let values: Vec<V> = operands.iter().map(|operand| operand.value).collect();
+        return Partial::exact(op.compute(&values));
+    }
+
+    // For gates with a single operand, b is just a again
+    let (a, b) = (operands[0], operands[operands.len() - 1]);
+    match *op {
+        Operation::Set { .. } => a,
+        Operation::Not { .. } => a.not(),
+        Operation::And { .. } => a.and(b),
+        Operation::Or { .. } => a.or(b),
+        Operation::Nand { .. } => a.and(b).not(),
+        Operation::Nor { .. } => a.or(b).not(),
+        Operation::Xor { .. } => {
+            let known = a.known & b.known;
+            Partial {
+                known,
+                value: (a.value ^ b.value) & known,
+            }
+        }
+        // The carries only go upwards, so the bits below the lowest unknown
+        // one are known
+        Operation::Add { .. } | Operation::Sub { .. } => {
+            let known = V::low_bits((a.known & b.known).trailing_ones());
+            Partial {
+                known,
+                value: op.compute(&[a.value, b.value]) & known,
+            }
+        }
+        // The bits shifted in are known zeros
+        Operation::LShift { .. } | Operation::RShift { .. } if b.is_exact() => Partial {
+            known: !op.compute(&[!a.known, b.value]),
+            value: op.compute(&[a.value, b.value]),
+        },
+        Operation::LShift { .. } | Operation::RShift { .. } => {
+            if a.zeros() == !V::ZERO {
+                Partial::exact(V::ZERO)
+            } else {
+                Partial {
+                    known: V::ZERO,
+                    value: V::ZERO,
+                }
+            }
+        }
+    }
+}
+
+// Where a gate's operand comes from
+enum Source<V> {
+    Value(V),
+    Gate(usize),
+}
+
+// The part of the circuit the target depends on, with the free wires cut
+// loose from the gates driving them
+struct Search<'a, V: 'a> {
+    // In the circuit's order, each with where its operands come from
+    gates: Vec<(&'a Operation<V>, Vec<Source<V>>)>,
+    // Which free wire, if any, each gate drives
+    free: Vec<Option<usize>>,
+    target: usize,
+}
+
+impl<'a, V: Word> Search<'a, V> {
+    fn new(circuit: &'a Circuit<V>, target: &str, free: &[&str]) -> Option<Search<'a, V>> {
+        let drivers: HashMap<&str, &Operation<V>> = circuit
+            .gates()
+            .iter()
+            .map(|gate| (gate.output().as_str(), gate))
+            .collect();
+
+        let mut needed: HashSet<&str> = free.iter().cloned().collect();
+        let mut stack: Vec<&str> = vec![target];
+        while let Some(wire) = stack.pop() {
+            let gate = drivers.get(wire)?;
+            if needed.insert(wire) {
+                stack.extend(inputs(gate).into_iter().map(|input| input.as_str()));
+            }
+        }
+        if free.iter().any(|wire| !drivers.contains_key(wire)) {
+            return None;
+        }
+
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut search = Search {
+            gates: vec![],
+            free: vec![],
+            target: 0,
+        };
+        for gate in circuit.gates() {
+            let wire = gate.output();
+            if !needed.contains(wire.as_str()) {
+                continue;
+            }
+
+            let slot = free.iter().position(|&name| name == wire);
+            let sources = match slot {
+                // Free wires are driven by the search, not their gate
+                Some(_) => vec![],
+                None => gate
+                    .operands()
+                    .into_iter()
+                    .map(|operand| match *operand {
+                        Operand::OpWire { ref name } => Source::Gate(index[name.as_str()]),
+                        Operand::OpValue { value } => Source::Value(value),
+                    })
+                    .collect(),
+            };
+
+            index.insert(wire.as_str(), search.gates.len());
+            search.gates.push((gate, sources));
+            search.free.push(slot);
+        }
+
+        search.target = index[target];
+        Some(search)
+    }
+
+    fn evaluate(&self, assigned: &[Partial<V>]) -> Partial<V> {
+        let mut signals: Vec<Partial<V>> = Vec::with_capacity(self.gates.len());
+
+        for (i, &(gate, ref sources)) in self.gates.iter().enumerate() {
+            let signal = match self.free[i] {
+                Some(slot) => assigned[slot],
+                None => {
+                    let operands: Vec<Partial<V>> = sources
+                        .iter()
+                        .map(|source| match *source {
+                            Source::Value(value) => Partial::exact(value),
+                            Source::Gate(g) => signals[g],
+                        })
+                        .collect();
+                    compute(gate, &operands)
+                }
+            };
+            signals.push(signal);
+        }
+
+        signals[self.target]
+    }
+
+    // Assign the remaining bits, `done` of them having been assigned already
+    fn search(&self, assigned: &mut Vec<Partial<V>>, done: u32, value: V, found: &mut Vec<Vec<V>>) {
+        let signal = self.evaluate(assigned);
+        if (signal.value ^ value) & signal.known != V::ZERO {
+            return;
+        }
+
+        let count = assigned.len() as u32;
+        if done == V::BITS * count {
+            found.push(assigned.iter().map(|partial| partial.value).collect());
+            return;
+        }
+
+        // Every free wire's lowest bit first, then their next bits, and so on
+        let (bit, slot) = (done / count, (done % count) as usize);
+        let mask = V::low_bits(bit + 1) & !V::low_bits(bit);
+        let before = assigned[slot];
+
+        for &set in &[V::ZERO, mask] {
+            assigned[slot] = Partial {
+                known: before.known | mask,
+                value: before.value | set,
+            };
+            self.search(assigned, done + 1, value, found);
+        }
+        assigned[slot] = before;
+    }
+}
+
+/// Every assignment of values to the free wires which puts the value on the
+/// target wire, each listing the values in the same order as `free`, and
+/// sorted. The free wires are driven by the assignment instead of their gates,
+/// as if overridden. None if any of the wires isn't in the circuit.
+///
+/// Any free wire which the target doesn't depend on can take every value, so
+/// the number of assignments multiplies by the number of values of a word.
+pub fn solve<V: Word>(
+    circuit: &Circuit<V>,
+    target: &str,
+    value: V,
+    free: &[&str],
+) -> Option<Vec<Vec<V>>> {
+    let search = Search::new(circuit, target, free)?;
+    let mut assigned = vec![
+        Partial {
+            known: V::ZERO,
+            value: V::ZERO,
+        };
+        free.len()
+    ];
+    let mut found = vec![];
+
+    search.search(&mut assigned, 0, value, &mut found);
+    found.sort();
+    trace!(
+        "{} assignments of {:?} put {} on {}",
+        found.len(),
+        free,
+        value,
+        target
+    );
+    Some(found)
+}
+
+#[cfg(test)]
+fn circuit<V: Word>(ops: &[&str]) -> Circuit<V> {
+    let ops = ops
+        .iter()
+        .map(|op| super::parse_operation(op).unwrap())
+        .collect();
+    Circuit::new(ops).unwrap()
+}
+
+#[test]
+fn test_solve() {
+    let shifted: Circuit<u8> = circuit(&["0 -> x", "x LSHIFT 1 -> d", "d XOR 5 -> a"]);
+    assert_eq!(
+        Some(vec![vec![1], vec![129]]),
+        solve(&shifted, "a", 7, &["x"])
+    );
+    assert_eq!(Some(vec![]), solve(&shifted, "a", 6, &["x"]));
+    // Overriding d cuts x off from a altogether
+    assert_eq!(Some(vec![vec![2]]), solve(&shifted, "a", 7, &["d"]));
+    assert_eq!(None, solve(&shifted, "a", 7, &["y"]));
+    assert_eq!(None, solve(&shifted, "z", 7, &["x"]));
+
+    let sum: Circuit<u8> = circuit(&["0 -> x", "0 -> y", "x ADD y -> s", "s RSHIFT 4 -> a"]);
+    let found = solve(&sum, "a", 3, &["x", "y"]).unwrap();
+    assert_eq!(16 * 256, found.len());
+    assert!(found.iter().all(|xy| xy[0].wrapping_add(xy[1]) >> 4 == 3));
+}
+
+#[test]
+fn test_solve_16_bits() {
+    let circuit: Circuit = circuit(&[
+        "0 -> b",
+        "b RSHIFT 2 -> c",
+        "b AND c -> d",
+        "NOT d -> e",
+        "e OR 1 -> a",
+    ]);
+    let found = solve(&circuit, "a", 65533, &["b"]).unwrap();
+
+    assert!(!found.is_empty());
+    for b in found {
+        let mut sim = super::circuit::Simulation::new(&circuit);
+        sim.override_wire("b", b[0]);
+        assert_eq!(Some(65533), sim.signal("a"));
+    }
+}
@@ -0,0 +1,87 @@
+// The signals carried by a circuit's wires. The booklet's wires are 16 bits
+// wide, but the gates work the same on any width of word, so the simulator
+// is generic over them.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+use std::str::FromStr;
+
+/// An unsigned word of a fixed number of bits. Arithmetic wraps around, and
+/// shifting by the width or more leaves nothing.
+pub trait Word:
+    Copy
+    + Debug
+    + Display
+    + Eq
+    + Ord
+    + Hash
+    + FromStr
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    const BITS: u32;
+    const ZERO: Self;
+
+    fn shift_left(self, by: Self) -> Self;
+    fn shift_right(self, by: Self) -> Self;
+    fn wrapping_add(self, other: Self) -> Self;
+    fn wrapping_sub(self, other: Self) -> Self;
+    fn trailing_ones(self) -> u32;
+    /// The word with only its lowest `count` bits set.
+    fn low_bits(count: u32) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($t:ident)*) => {
+        $(
+            impl Word for $t {
+                const BITS: u32 = $t::BITS;
+                const ZERO: $t = 0;
+
+                fn shift_left(self, by: $t) -> $t {
+                    self.checked_shl(by as u32).filter(|_| (by as u64) < 64).unwrap_or(0)
+                }
+
+                fn shift_right(self, by: $t) -> $t {
+                    self.checked_shr(by as u32).filter(|_| (by as u64) < 64).unwrap_or(0)
+                }
+
+                fn wrapping_add(self, other: $t) -> $t {
+                    $t::wrapping_add(self, other)
+                }
+
+                fn wrapping_sub(self, other: $t) -> $t {
+                    $t::wrapping_sub(self, other)
+                }
+
+                fn trailing_ones(self) -> u32 {
+                    $t::trailing_ones(self)
+                }
+
+                fn low_bits(count: u32) -> $t {
+                    if count >= $t::BITS {
+                        !0
+                    } else {
+                        (1 << count) - 1
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8 u16 u32 u64);
+
+#[test]
+fn test_shifts() {
+    assert_eq!(0x80u8, 1u8.shift_left(7));
+    assert_eq!(0u8, 1u8.shift_left(8));
+    assert_eq!(0u16, 0x8000u16.shift_right(16));
+    assert_eq!(0u64, 1u64.shift_left(1 << 32));
+    assert_eq!(1u64 << 40, 1u64.shift_left(40));
+    assert_eq!(0x0fu8, u8::low_bits(4));
+    assert_eq!(u32::MAX, u32::low_bits(32));
+}
@@ -39,7 +39,7 @@ string code (2 + 5 + 10 + 6 = 23) minus the total number of characters in memory
 for string values (0 + 3 + 7 + 1 = 11) is 23 - 11 = 12.
 */
 
-use std::io::{self, BufRead};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 fn count_difference(s: &String) -> i32 {
     let bs = s.to_owned().into_bytes();
@@ -77,6 +77,12 @@ fn count_in_memory(s: &Vec<u8>) -> usize {
     count
 }
 
+// Length of the string once encoded as a literal, which quotes it again and
+// escapes every quote and backslash
+fn count_encoded(s: &str) -> usize {
+    s.chars().filter(|&c| c == '"' || c == '\\').count() + s.len() + 2
+}
+
 #[allow(dead_code)]
 fn test1() {
     fn print_len(s: &String) {
@@ -90,19 +96,35 @@ fn test1() {
     print_len(&"\"aaa\\\"aaa\"".to_owned());
 }
 
-pub fn problem() {
-    println!("2015, day 8");
+pub struct Day8;
 
-    let mut sum = 0;
+impl Solution for Day8 {
+    type Input = Vec<String>;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input, |line| {
+            if line.len() >= 2 && line.starts_with('"') && line.ends_with('"') {
+                Ok(line.to_owned())
+            } else {
+                Err(ParseError::new(line, "a quoted string").at(0, 1))
+            }
+        })
+    }
 
-        sum += count_difference(&sline);
+    fn part1(&self, input: &Vec<String>) -> Answer {
+        input
+            .iter()
+            .fold(0, |sum, s| sum + count_difference(s))
+            .into()
     }
 
-    println!("Total difference: {}", sum);
+    fn part2(&self, input: &Vec<String>) -> Answer {
+        input
+            .iter()
+            .map(|s| count_encoded(s) - s.len())
+            .sum::<usize>()
+            .into()
+    }
 }
 
 #[test]
@@ -111,4 +133,9 @@ fn test_example() {
     assert_eq!(count_in_memory_str(&"\"\"".to_owned()), 0);
     assert_eq!(count_in_memory_str(&"\"abc\"".to_owned()), 3);
     assert_eq!(count_in_memory_str(&"\"aaa\\\"aaa\"".to_owned()), 7);
+
+    assert_eq!(count_encoded("\"\""), 6);
+    assert_eq!(count_encoded("\"abc\""), 9);
+    assert_eq!(count_encoded("\"aaa\\\"aaa\""), 16);
+    assert_eq!(count_encoded("\"\\x27\""), 11);
 }
@@ -29,158 +29,117 @@ What is the distance of the shortest route?
 */
 
 extern crate permutohedron;
-extern crate regex;
 
-use std::io::{self, BufRead};
-use std::collections::HashMap;
-use self::regex::Regex;
 use self::permutohedron::heap_recursive;
+use advent_core::grammar::{after, name, number, parse_line};
+use advent_core::graph::{Edges, Goal, Graph, Shape, MAX_NODES};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 type Distance = i32;
 type CityPair = (String, String);
-type DistanceMap = HashMap<CityPair, Distance>;
+type DistanceMap = Graph<Distance>;
 
-#[allow(dead_code)]
-fn distance(map: &DistanceMap, from: &String, to: &String) -> Option<Distance> {
-    match map.get(&(from.to_owned(), to.to_owned())) {
-        Some(d) => Some(*d),
-        None => None,
-    }
-}
-
-fn parse_distance_spec(s: &String) -> Option<(CityPair, Distance)> {
-    lazy_static! {
-        static ref R_DISTANCE_SPEC: Regex = Regex::new(r"(\w+) to (\w+) = (\d+)").unwrap();
-    }
-
-    if R_DISTANCE_SPEC.is_match(s) {
-        for cap in R_DISTANCE_SPEC.captures_iter(s) {
-            let city1: String = cap[1].to_owned();
-            let city2: String = cap[2].to_owned();
+fn parse_distance_spec(s: &str) -> Result<(CityPair, Distance), ParseError> {
+    let spec = (name(), after("to", name()), after("=", number()));
+    let (city1, city2, d) = parse_line(s, spec)?;
 
-            match cap[3].parse::<Distance>() {
-                Ok(d) => return Some(((city1, city2), d)),
-                Err(_) => return None,
-            }
-        }
-    }
-
-    None
+    Ok(((city1, city2), d))
 }
 
-fn update_distance_map(map: &mut DistanceMap, s: &String) -> bool {
-    match parse_distance_spec(s) {
-        Some(((c1, c2), d)) => {
-            map.insert((c1.to_owned(), c2.to_owned()), d);
-            map.insert((c2.to_owned(), c1.to_owned()), d);
-            true
-        }
-        None => false,
+// The shortest or the longest route visiting every city exactly once
+fn solve(map: &DistanceMap, goal: Goal) -> Answer {
+    let route = map.best(Shape::Path, goal);
+    if let Some(ref route) = route {
+        let cities: Vec<&str> = route.nodes.iter().map(|&city| map.name(city)).collect();
+        debug!("{} = {}", cities.join(" -> "), route.weight);
     }
+    route.map(|route| route.weight).into()
 }
 
-// Get a vector of unique cities from the distance map
-fn get_cities(map: &DistanceMap) -> Vec<String> {
-    let mut cities: HashMap<String, String> = HashMap::new();
-    let mut cv: Vec<String> = vec![];
-
-    for (&(ref c1, ref c2), _) in map.iter() {
-        cities.insert(c1.to_owned(), c1.to_owned());
-        cities.insert(c2.to_owned(), c2.to_owned());
-    }
-
-    for city in cities.keys() {
-        cv.push(city.to_owned());
-    }
-    cv
-}
+pub struct Day9;
 
-fn calculate_shortest(map: &DistanceMap, cities: &Vec<String>) -> (Distance, Distance) {
-    let mut shortest: Option<Distance> = None;
-    let mut longest: Option<Distance> = None;
+impl Solution for Day9 {
+    type Input = DistanceMap;
 
-    let mut mcities: Vec<String> = cities.clone();
-    let mut city_permutations = Vec::new();
-
-    heap_recursive(&mut mcities, |city_perm| {
-        city_permutations.push(city_perm.to_vec())
-    });
-
-    for city_perm in city_permutations {
-        let mut sum_distance = 0;
-        for city_pair in city_perm.iter().zip(city_perm[1..].iter()) {
-            let (c1, c2) = city_pair;
-            sum_distance += map.get(&(c1.to_owned(), c2.to_owned())).unwrap_or(&0);
+    fn parse(&self, input: &str) -> Result<DistanceMap, ParseError> {
+        let mut distance_map = DistanceMap::new(Edges::Undirected);
+        for ((city1, city2), d) in parse_lines(input, parse_distance_spec)? {
+            distance_map.add_edge(&city1, &city2, d);
         }
 
-        if shortest == None {
-            shortest = Some(sum_distance);
-        } else if sum_distance < shortest.unwrap() {
-            shortest = Some(sum_distance);
+        // Too many cities to search through every route
+        if distance_map.len() > MAX_NODES {
+            return Err(ParseError::new(
+                format!("{} cities", distance_map.len()),
+                format!("at most {} cities", MAX_NODES),
+            ));
         }
 
-        if longest == None {
-            longest = Some(sum_distance);
-        } else if sum_distance > longest.unwrap() {
-            longest = Some(sum_distance);
-        }
+        Ok(distance_map)
     }
 
-    (shortest.unwrap_or(-1), longest.unwrap_or(-1))
-}
-
-pub fn problem() {
-    println!("2015, day 9");
-
-    // test_permutations();
-    // return;
-
-    let mut distance_map = DistanceMap::new();
-
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-
-        if !update_distance_map(&mut distance_map, &sline) {
-            println!("Invalid distance spec: {}", sline);
-        }
+    fn part1(&self, input: &DistanceMap) -> Answer {
+        solve(input, Goal::Min)
     }
 
-    let cities = get_cities(&distance_map);
-    let (shortest, longest) = calculate_shortest(&distance_map, &cities);
-    println!("Shortest: {}, Longest: {}", shortest, longest);
+    fn part2(&self, input: &DistanceMap) -> Answer {
+        solve(input, Goal::Max)
+    }
 }
 
 #[test]
 fn test_parse_distance_spec() {
-    match parse_distance_spec(&"London to Dublin = 464".to_owned()) {
-        Some(((c1, c2), d)) => {
+    match parse_distance_spec("London to Dublin = 464") {
+        Ok(((c1, c2), d)) => {
             assert_eq!("London", c1);
             assert_eq!("Dublin", c2);
             assert_eq!(464, d);
         }
-        None => assert!(false),
+        Err(_) => assert!(false),
     }
 
-    match parse_distance_spec(&"London to Belfast = 518".to_owned()) {
-        Some(((c1, c2), d)) => {
+    match parse_distance_spec("London to Belfast = 518") {
+        Ok(((c1, c2), d)) => {
             assert_eq!("London", c1);
             assert_eq!("Belfast", c2);
             assert_eq!(518, d);
         }
-        None => assert!(false),
+        Err(_) => assert!(false),
     }
 
-    match parse_distance_spec(&"Dublin to Belfast = 141".to_owned()) {
-        Some(((c1, c2), d)) => {
+    match parse_distance_spec("Dublin to Belfast = 141") {
+        Ok(((c1, c2), d)) => {
             assert_eq!("Dublin", c1);
             assert_eq!("Belfast", c2);
             assert_eq!(141, d);
         }
-        None => assert!(false),
+        Err(_) => assert!(false),
     }
 }
 
+#[test]
+fn test_routes() {
+    let map = Day9
+        .parse("London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141")
+        .unwrap();
+    assert_eq!(Answer::from(605), Day9.part1(&map));
+    assert_eq!(Answer::from(982), Day9.part2(&map));
+
+    // Nothing joins the two pairs of cities up
+    let map = Day9
+        .parse("London to Dublin = 464\nParis to Rome = 1100")
+        .unwrap();
+    assert_eq!(Answer::from(None::<Distance>), Day9.part1(&map));
+
+    let chain: Vec<String> = (0..30)
+        .map(|n| format!("C{} to C{} = 1", n, n + 1))
+        .collect();
+    assert_eq!(
+        Err(ParseError::new("31 cities", "at most 20 cities")),
+        Day9.parse(&chain.join("\n")).map(|_| ())
+    );
+}
+
 #[allow(dead_code)]
 // Playground method to test slices, zippers and permutations in Rust
 fn test_permutations() {
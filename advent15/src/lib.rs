@@ -0,0 +1,63 @@
+#[macro_use] extern crate log;
+#[macro_use] extern crate ndarray;
+extern crate advent_core;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+
+use advent_core::Registry;
+
+// The day 6 light grids, the day 7 circuit simulator, the day 14 race, the day
+// 15 recipe search and the day 18 cellular automata are useful beyond the
+// puzzles themselves
+pub use day6::{compressed, count_lights, parse_command, run_board, Board, Command, Grid, Instructions, Lights, Mode, Pos};
+pub use day7::{circuit, export, parse_operation, synthesis, word, Operand, Operation, Signals, Value, Wire};
+pub use day14::{timeline_csv, Race, Reindeer, Standing};
+pub use day15::{best_recipes, parse_ingredient, Bound, Constraints, Ingredient, Property, Recipe};
+pub use day18::automaton;
+
+// Add all of 2015's solutions to the given registry
+pub fn register(registry: &mut Registry) {
+    registry.register(2015, 1, "Not Quite Lisp", day1::Day1);
+    registry.register(2015, 2, "I Was Told There Would Be No Math", day2::Day2);
+    registry.register(2015, 3, "Perfectly Spherical Houses in a Vacuum", day3::Day3);
+    registry.register(2015, 4, "The Ideal Stocking Stuffer", day4::Day4);
+    registry.register(2015, 5, "Doesn't He Have Intern-Elves For This?", day5::Day5);
+    registry.register(2015, 6, "Probably a Fire Hazard", day6::Day6);
+    registry.register(2015, 7, "Some Assembly Required", day7::Day7);
+    registry.register(2015, 8, "Matchsticks", day8::Day8);
+    registry.register(2015, 9, "All in a Single Night", day9::Day9);
+    registry.register(2015, 10, "Elves Look, Elves Say", day10::Day10);
+    registry.register(2015, 11, "Corporate Policy", day11::Day11);
+    registry.register(2015, 12, "JSAbacusFramework.io", day12::Day12);
+    registry.register(2015, 13, "Knights of the Dinner Table", day13::Day13);
+    registry.register(2015, 14, "Reindeer Olympics", day14::Day14);
+    registry.register(2015, 15, "Science for Hungry People", day15::Day15);
+    registry.register(2015, 16, "Aunt Sue", day16::Day16);
+    registry.register(2015, 17, "No Such Thing as Too Much", day17::Day17);
+    registry.register(2015, 18, "Like a GIF For Your Yard", day18::Day18);
+    registry.register(2015, 19, "Medicine for Rudolph", day19::Day19);
+    registry.register(2015, 20, "Infinite Elves and Infinite Houses", day20::Day20);
+    registry.register(2015, 21, "RPG Simulator 20XX", day21::Day21);
+    registry.register(2015, 22, "Wizard Simulator 20XX", day22::Day22);
+}
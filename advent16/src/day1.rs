@@ -22,7 +22,9 @@ For example:
 
 How many blocks away is Easter Bunny HQ?
 */
-use std::io::{self, BufRead};
+use std::collections::HashSet;
+use advent_core::grammar::{number, one_of, parse_line, separated, Parser};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 #[derive(Debug)]
 enum Direction {
@@ -40,7 +42,7 @@ struct Pos {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum Side {
+pub enum Side {
     R(i32),
     L(i32),
 }
@@ -114,56 +116,90 @@ fn update_positions(pos: Pos, sides: &[Side]) -> Pos {
     sides.iter().fold(pos, |p, s| update_pos(p, s.to_owned()))
 }
 
-fn parse_side(s: &str) -> Option<Side> {
-    if s.starts_with("R") {
-        let num_part: String = s.chars().skip(1).collect();
-        if let Ok(num) = num_part.parse::<i32>() {
-            return Some(Side::R(num));
-        }
-    } else if s.starts_with("L") {
-        let num_part: String = s.chars().skip(1).collect();
-        if let Ok(num) = num_part.parse::<i32>() {
-            return Some(Side::L(num));
+// Follow the turns a block at a time, stopping at the first intersection
+// which is visited twice
+fn first_revisit(sides: &[Side]) -> Option<Pos> {
+    let mut visited: HashSet<(i32, i32)> = HashSet::new();
+    let mut pos = START_POS;
+    visited.insert((pos.x, pos.y));
+
+    for side in sides {
+        // Turn on the spot, then walk
+        let (turned, blocks) = match *side {
+            Side::R(n) => (update_pos(pos, Side::R(0)), n),
+            Side::L(n) => (update_pos(pos, Side::L(0)), n),
+        };
+        pos = turned;
+
+        let (dx, dy) = match pos.direction {
+            Direction::North => (0, 1),
+            Direction::East => (1, 0),
+            Direction::South => (0, -1),
+            Direction::West => (-1, 0),
+        };
+
+        for _ in 0..blocks {
+            pos.x += dx;
+            pos.y += dy;
+            if !visited.insert((pos.x, pos.y)) {
+                return Some(pos);
+            }
         }
     }
 
     None
 }
 
-fn parse_sides(s: &str, sides: &mut Vec<Side>) {
-    for x in s.split(",") {
-        if let Some(d) = parse_side(x.trim()) {
-            sides.push(d);
-        }
-    }
+// A turn like "R2"
+fn side() -> impl Parser<Side> {
+    (one_of(&["R", "L"]), number()).map(|(turn, blocks)| match turn {
+        "R" => Side::R(blocks),
+        _ => Side::L(blocks),
+    })
+}
+
+#[cfg(test)]
+fn parse_side(s: &str) -> Result<Side, ParseError> {
+    parse_line(s, side())
+}
+
+// Parse a comma separated list of turns like "R2, L3"
+fn parse_sides(s: &str) -> Result<Vec<Side>, ParseError> {
+    parse_line(s, separated(side(), ","))
 }
 
-pub fn problem() {
-    let mut sides: Vec<Side> = vec![];
+pub struct Day1;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day1 {
+    type Input = Vec<Side>;
 
-        parse_sides(&sline, &mut sides);
+    fn parse(&self, input: &str) -> Result<Vec<Side>, ParseError> {
+        Ok(parse_lines(input, parse_sides)?.concat())
     }
 
-    println!("There {} sides", sides.len());
+    fn part1(&self, input: &Vec<Side>) -> Answer {
+        let pos = update_positions(START_POS, input);
+        distance(&pos).into()
+    }
 
-    let mut pos = START_POS;
-    pos = update_positions(pos, &sides);
-    println!("Final pos: {:?}", pos);
-    println!("Final distance: {}", distance(&pos));
+    fn part2(&self, input: &Vec<Side>) -> Answer {
+        first_revisit(input).map(|pos| distance(&pos)).into()
+    }
 }
 
 #[test]
 fn test_parse_side() {
-    assert_eq!(Some(Side::R(3)), parse_side("R3"));
-    assert_eq!(Some(Side::L(8)), parse_side("L8"));
-    assert_eq!(None, parse_side("LL"));
-    assert_eq!(None, parse_side("P"));
-    assert_eq!(None, parse_side(""));
-    assert_eq!(None, parse_side("8"));
+    assert_eq!(Ok(Side::R(3)), parse_side("R3"));
+    assert_eq!(Ok(Side::L(8)), parse_side("L8"));
+    assert!(parse_side("LL").is_err());
+    assert!(parse_side("P").is_err());
+    assert!(parse_side("").is_err());
+    assert!(parse_side("8").is_err());
+
+    assert_eq!(
+        Err(ParseError::new("Rx", "\"R\" or \"L\"").at(0, 9)),
+        parse_sides("R2, L3, Rx, L1")
+    );
 }
 
 #[test]
@@ -193,3 +229,11 @@ fn test_examples() {
         assert_eq!(0, distance(&pos));
     }
 }
+
+#[test]
+fn test_first_revisit() {
+    let pos = first_revisit(&[Side::R(8), Side::R(4), Side::R(4), Side::R(8)]);
+    assert_eq!(Some(4), pos.map(|p| distance(&p)));
+
+    assert!(first_revisit(&[Side::R(2), Side::L(3)]).is_none());
+}
@@ -43,7 +43,7 @@ UUUUD
 Your puzzle input is the instructions from the document you found at the front
 desk. What is the bathroom code?
 */
-use std::io::{self, BufRead};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 const BOARD_SIZE: i32 = 3;
 
@@ -75,7 +75,6 @@ fn process_instr(mut p: Pos, instr: &str) -> (Pos, i32) {
     (p, pos_to_digit(p))
 }
 
-#[allow(dead_code)]
 fn process_code(mut p: Pos, instrs: &[&str]) -> String {
     let mut code: String = String::new();
 
@@ -88,20 +87,76 @@ fn process_code(mut p: Pos, instrs: &[&str]) -> String {
     code
 }
 
-pub fn problem() {
-    let mut pos: Pos = (1, 1); // we start at 5th digit
+// The keypad as actually designed, with gaps where there are no buttons
+const DIAMOND_KEYPAD: [&str; 5] = ["  1  ", " 234 ", "56789", " ABC ", "  D  "];
 
-    print!("Code: ");
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+fn key_at(keypad: &[&str], p: Pos) -> Option<char> {
+    let (x, y) = p;
+    if x < 0 || y < 0 {
+        return None;
+    }
+
+    keypad
+        .get(y as usize)
+        .and_then(|row| row.chars().nth(x as usize))
+        .filter(|&c| c != ' ')
+}
+
+// Like move_pos, but a move is ignored if it doesn't lead to a button
+fn move_on_keypad(keypad: &[&str], p: Pos, d: char) -> Pos {
+    let (x, y) = p;
+    let next = match d {
+        'R' => (x + 1, y),
+        'L' => (x - 1, y),
+        'U' => (x, y - 1),
+        'D' => (x, y + 1),
+        _ => p,
+    };
+
+    if key_at(keypad, next).is_some() {
+        next
+    } else {
+        p
+    }
+}
+
+fn process_keypad_code(keypad: &[&str], mut p: Pos, instrs: &[&str]) -> String {
+    let mut code: String = String::new();
+
+    for instr in instrs {
+        for c in instr.chars() {
+            p = move_on_keypad(keypad, p, c);
+        }
+        code.extend(key_at(keypad, p));
+    }
+
+    code
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input, |line| {
+            let line = line.trim_end();
+            match line.char_indices().find(|&(_, c)| !"UDLR".contains(c)) {
+                Some((i, c)) => Err(ParseError::new(c.to_string(), "one of \"UDLR\"").at(0, i + 1)),
+                None => Ok(line.to_owned()),
+            }
+        })
+    }
 
-        let (new_pos, digit) = process_instr(pos, &sline);
-        pos = new_pos;
-        print!("{}", digit);
+    fn part1(&self, input: &Vec<String>) -> Answer {
+        let instrs: Vec<&str> = input.iter().map(|s| s.as_str()).collect();
+        process_code((1, 1), &instrs).into() // we start at 5th digit
     }
 
-    println!("");
+    fn part2(&self, input: &Vec<String>) -> Answer {
+        let instrs: Vec<&str> = input.iter().map(|s| s.as_str()).collect();
+        process_keypad_code(&DIAMOND_KEYPAD, (0, 2), &instrs).into() // 5 again
+    }
 }
 
 #[test]
@@ -139,3 +194,13 @@ fn test_example() {
     let code = process_code(p, &instrs);
     assert_eq!("1985", code);
 }
+
+#[test]
+fn test_diamond_keypad() {
+    assert_eq!(Some('D'), key_at(&DIAMOND_KEYPAD, (2, 4)));
+    assert_eq!(None, key_at(&DIAMOND_KEYPAD, (0, 0)));
+    assert_eq!((0, 2), move_on_keypad(&DIAMOND_KEYPAD, (0, 2), 'U'));
+
+    let instrs = vec!["ULL", "RRDDD", "LURDL", "UUUUD"];
+    assert_eq!("5DB3", process_keypad_code(&DIAMOND_KEYPAD, (0, 2), &instrs));
+}
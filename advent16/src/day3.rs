@@ -19,60 +19,57 @@ not larger than 25.
 
 In your puzzle input, how many of the listed triangles are possible?
 */
-use std::io::{self, BufRead};
+use advent_core::grammar::{number, parse_line};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 fn valid_triangle(x: i32, y: i32, z: i32) -> bool {
     (x + y > z) && (z + y > x) && (x + z > y)
 }
 
-fn parse_triangle_spec(s: &str) -> Option<(i32, i32, i32)> {
-    let mut nums: Vec<i32> = vec![];
-
-    for ns in s.split(" ") {
-        if nums.len() > 3 {
-            // we should only have 3 sides
-            return None;
-        }
+fn parse_triangle_spec(s: &str) -> Result<(i32, i32, i32), ParseError> {
+    parse_line(s, (number(), number(), number()))
+}
 
-        ns.trim();
-        if ns.len() == 0 {
-            continue;
-        }
+// Read the specs by columns instead: every three rows hold three triangles,
+// one down each column
+fn by_columns(specs: &[(i32, i32, i32)]) -> Vec<(i32, i32, i32)> {
+    let mut triangles = vec![];
 
-        if let Ok(num) = ns.parse::<i32>() {
-            nums.push(num);
-        } else {
-            return None;
+    for rows in specs.chunks(3) {
+        if let [(a1, b1, c1), (a2, b2, c2), (a3, b3, c3)] = *rows {
+            triangles.push((a1, a2, a3));
+            triangles.push((b1, b2, b3));
+            triangles.push((c1, c2, c3));
         }
     }
 
-    if nums.len() == 3 {
-        Some((nums[0], nums[1], nums[2]))
-    } else {
-        None
-    }
+    triangles
 }
 
-pub fn problem() {
-    let mut valid_count = 0;
-
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-
-        match parse_triangle_spec(&sline) {
-            Some((x, y, z)) => {
-                if valid_triangle(x, y, z) {
-                    valid_count += 1;
-                }
-            }
-            None => {
-                println!("Invalid triangle spec: {}", sline);
-            }
-        }
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<(i32, i32, i32)>;
+
+    fn parse(&self, input: &str) -> Result<Vec<(i32, i32, i32)>, ParseError> {
+        parse_lines(input, parse_triangle_spec)
+    }
+
+    fn part1(&self, input: &Vec<(i32, i32, i32)>) -> Answer {
+        input
+            .iter()
+            .filter(|&&(x, y, z)| valid_triangle(x, y, z))
+            .count()
+            .into()
     }
 
-    println!("There are {} valid triangles", valid_count);
+    fn part2(&self, input: &Vec<(i32, i32, i32)>) -> Answer {
+        by_columns(input)
+            .iter()
+            .filter(|&&(x, y, z)| valid_triangle(x, y, z))
+            .count()
+            .into()
+    }
 }
 
 #[test]
@@ -90,6 +87,15 @@ fn test_valid_triangle() {
 
 #[test]
 fn test_parse_triangle() {
-    assert_eq!(Some((1, 2, 3)), parse_triangle_spec("1 2 3"));
-    assert_eq!(Some((13, 72, 34)), parse_triangle_spec("13 72 34"));
+    assert_eq!(Ok((1, 2, 3)), parse_triangle_spec("1 2 3"));
+    assert_eq!(Ok((13, 72, 34)), parse_triangle_spec("13 72 34"));
+}
+
+#[test]
+fn test_by_columns() {
+    let specs = vec![(101, 301, 501), (102, 302, 502), (103, 303, 503)];
+    assert_eq!(
+        vec![(101, 102, 103), (301, 302, 303), (501, 502, 503)],
+        by_columns(&specs)
+    );
 }
@@ -24,15 +24,13 @@ Of the real rooms from the list above, the sum of their sector IDs is 1514.
 What is the sum of the sector IDs of the real rooms?
 */
 
-extern crate regex;
-
 use std::cmp::Ordering;
-use self::regex::Regex;
-use std::io::{self, BufRead};
 use std::collections::HashMap;
+use advent_core::grammar::{after, chars, keyword, number, parse_line};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 #[derive(Debug)]
-struct Entry {
+pub struct Entry {
     data: String,
     sector_id: i32,
     hash: String,
@@ -71,56 +69,73 @@ impl Entry {
         hash
     }
 
-    fn parse_entry(s: &str) -> Option<Entry> {
-        lazy_static! {
-            static ref R_ENTRY: Regex = Regex::new(r"([a-z,-]*)-(\d+)\[(\w+)\]").unwrap();
-        }
-
-        if R_ENTRY.is_match(s) {
-            for cap in R_ENTRY.captures_iter(s) {
-                if let Ok(sector_id) = cap[2].parse::<i32>() {
-                    let entry = Entry {
-                        data: cap[1].to_string(),
-                        sector_id,
-                        hash: cap[3].to_string(),
-                    };
-                    return Some(entry);
-                }
-            }
-        }
+    // Shift every letter forward through the alphabet by the sector id, and
+    // turn dashes into spaces
+    fn decrypt_name(&self) -> String {
+        let shift = (self.sector_id % 26) as u8;
+
+        self.data
+            .chars()
+            .map(|c| match c {
+                '-' => ' ',
+                'a'..='z' => ((c as u8 - b'a' + shift) % 26 + b'a') as char,
+                _ => c,
+            })
+            .collect()
+    }
 
-        None
+    fn parse_entry(s: &str) -> Result<Entry, ParseError> {
+        let lowercase = |c: char| c.is_ascii_lowercase();
+        let entry = (
+            chars("an encrypted name", |c| lowercase(c) || c == '-'),
+            number(),
+            after("[", chars("a checksum", lowercase)),
+            keyword("]"),
+        );
+        let (data, sector_id, hash, ()) = parse_line(s, entry)?;
+
+        Ok(Entry {
+            // The dash before the sector ID isn't part of the name
+            data: data.trim_end_matches('-').to_owned(),
+            sector_id,
+            hash,
+        })
     }
 }
 
-pub fn problem() {
-    let mut sector_id_sum = 0;
+pub struct Day4;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day4 {
+    type Input = Vec<Entry>;
 
-        match Entry::parse_entry(&sline) {
-            Some(entry) => {
-                if entry.valid() {
-                    // only sum sector ids of valid entries
-                    sector_id_sum += entry.sector_id;
-                }
-            }
-            None => {
-                println!("Invalid entry line: {}", sline);
-            }
-        }
+    fn parse(&self, input: &str) -> Result<Vec<Entry>, ParseError> {
+        parse_lines(input, Entry::parse_entry)
+    }
+
+    fn part1(&self, input: &Vec<Entry>) -> Answer {
+        // only sum sector ids of valid entries
+        input
+            .iter()
+            .filter(|entry| entry.valid())
+            .fold(0, |sum, entry| sum + entry.sector_id)
+            .into()
     }
 
-    println!("Sector id sum: {}", sector_id_sum);
+    fn part2(&self, input: &Vec<Entry>) -> Answer {
+        input
+            .iter()
+            .filter(|entry| entry.valid())
+            .find(|entry| entry.decrypt_name() == "northpole object storage")
+            .map(|entry| entry.sector_id)
+            .into()
+    }
 }
 
 #[test]
 fn test_parse_entry() {
     let check_valid = |s, expect_valid| {
         let entry = Entry::parse_entry(s);
-        assert!(entry.is_some());
+        assert!(entry.is_ok());
         assert_eq!(expect_valid, entry.unwrap().valid());
     };
 
@@ -137,3 +152,9 @@ fn test_calculate_checksum() {
     assert_eq!("ab", Entry::calculate_checksum("aa-bbb-aa"));
     assert_eq!("ba", Entry::calculate_checksum("aa-bb-bbb-aa"));
 }
+
+#[test]
+fn test_decrypt_name() {
+    let entry = Entry::parse_entry("qzmt-zixmtkozy-ivhz-343[zimth]").unwrap();
+    assert_eq!("very encrypted name", entry.decrypt_name());
+}
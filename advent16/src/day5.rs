@@ -32,39 +32,64 @@ Your puzzle input is ugkcyxxp.
 extern crate md5;
 
 use std::u64;
-use std::io::{self, BufRead};
+use advent_core::{Answer, ParseError, Solution};
+
+// The hashes of the door id and an increasing index which start with five
+// zeros, as hex strings
+fn interesting_hashes(s: &str) -> impl Iterator<Item = String> + '_ {
+    (0..u64::MAX)
+        .map(move |num| md5::compute(format!("{}{}", s, num)))
+        // Five zero nibbles, checked before paying for the hex formatting
+        .filter(|digest| digest[0] == 0 && digest[1] == 0 && digest[2] & 0xf0 == 0)
+        .map(|digest| format!("{:x}", digest))
+}
 
 fn calculate_password(s: &str) -> String {
-    let mut password: String = String::new();
-    let mut iterations = 8;
+    interesting_hashes(s)
+        .filter_map(|hash| hash.chars().nth(5))
+        .take(8)
+        .collect()
+}
 
-    for num in 0..u64::MAX {
-        if iterations <= 0 {
-            break;
+// The second door's password: the sixth character of each hash gives a
+// position, and the seventh the character to put there. Only the first
+// character for each position counts, and invalid positions are skipped.
+fn calculate_positional_password(s: &str) -> String {
+    let mut password: [Option<char>; 8] = [None; 8];
+
+    for hash in interesting_hashes(s) {
+        let mut cs = hash.chars().skip(5);
+        if let (Some(pos), Some(c)) = (cs.next().and_then(|p| p.to_digit(16)), cs.next()) {
+            if let Some(slot) = password.get_mut(pos as usize) {
+                if slot.is_none() {
+                    *slot = Some(c);
+                }
+            }
         }
 
-        let src: String = format!("{}{}", s, num);
-        let digest = md5::compute(&src);
-        let hash: String = format!("{:x}", digest);
-
-        if hash.starts_with("00000") {
-            if let Some(c) = hash.chars().nth(5) {
-                password.push(c);
-                iterations -= 1;
-            }
+        if password.iter().all(|c| c.is_some()) {
+            break;
         }
     }
 
-    password
+    password.iter().filter_map(|&c| c).collect()
 }
 
-pub fn problem() {
-    let print_password = |s: &str| println!("Hash: {}, Password: {}", s, calculate_password(s));
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = String;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-        print_password(&sline);
+    fn parse(&self, input: &str) -> Result<String, ParseError> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(&self, input: &String) -> Answer {
+        calculate_password(input).into()
+    }
+
+    fn part2(&self, input: &String) -> Answer {
+        calculate_positional_password(input).into()
     }
 }
 
@@ -72,3 +97,10 @@ pub fn problem() {
 fn test_examples() {
     assert_eq!("18f47a30", calculate_password("abc"));
 }
+
+// Takes a while without optimisations, run with `cargo test -- --ignored`
+#[test]
+#[ignore]
+fn test_positional_example() {
+    assert_eq!("05ace8e3", calculate_positional_password("abc"));
+}
@@ -37,12 +37,12 @@ message, easter.
 Given the recording in your puzzle input, what is the error-corrected version of the message being sent?
 */
 
-use std::io::{self, BufRead};
 use std::collections::HashMap;
+use advent_core::{Answer, ParseError, Solution};
 
 type FreqMap = HashMap<char, i32>;
 
-struct Message {
+pub struct Message {
     frequencies: Vec<FreqMap>,
 }
 
@@ -89,11 +89,16 @@ impl Message {
         fchar
     }
 
-    pub fn get_decoded(&self) -> String {
+    fn get_least_frequent(fmap: &FreqMap) -> Option<char> {
+        fmap.iter().min_by_key(|&(_, v)| *v).map(|(c, _)| *c)
+    }
+
+    // Decode by picking a character from each position with `pick`
+    fn decode_with(&self, pick: fn(&FreqMap) -> Option<char>) -> String {
         let mut msg = String::new();
 
         for fmap in &self.frequencies {
-            if let Some(c) = Message::get_most_frequent(&fmap) {
+            if let Some(c) = pick(fmap) {
                 msg.push(c);
             }
         }
@@ -101,6 +106,15 @@ impl Message {
         msg
     }
 
+    pub fn get_decoded(&self) -> String {
+        self.decode_with(Message::get_most_frequent)
+    }
+
+    // The modified repetition code uses the least common characters instead
+    pub fn get_modified_decoded(&self) -> String {
+        self.decode_with(Message::get_least_frequent)
+    }
+
     pub fn new() -> Message {
         Message {
             frequencies: vec![],
@@ -108,17 +122,28 @@ impl Message {
     }
 }
 
-pub fn problem() {
-    let mut msg = Message::new();
+pub struct Day6;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
-        msg.add_message(&sline);
+impl Solution for Day6 {
+    type Input = Message;
+
+    fn parse(&self, input: &str) -> Result<Message, ParseError> {
+        let mut msg = Message::new();
+
+        for line in input.lines() {
+            msg.add_message(line);
+        }
+
+        Ok(msg)
     }
 
-    let decoded = msg.get_decoded();
-    println!("Decoded message: {}", decoded);
+    fn part1(&self, input: &Message) -> Answer {
+        input.get_decoded().into()
+    }
+
+    fn part2(&self, input: &Message) -> Answer {
+        input.get_modified_decoded().into()
+    }
 }
 
 #[test]
@@ -136,4 +161,5 @@ fn test_example() {
 
     let decoded = msg.get_decoded();
     assert_eq!("easter", decoded);
+    assert_eq!("advent", msg.get_modified_decoded());
 }
@@ -25,10 +25,10 @@ For example:
 How many IPs in your puzzle input support TLS?
 */
 
-use std::io::{self, BufRead};
+use advent_core::{parse_lines, Answer, ParseError, Solution};
 
 #[derive(Debug)]
-struct IP {
+pub struct IP {
     ant_seq: Vec<String>,      // ABBA annotations
     hypernet_seq: Vec<String>, // Hypernets
 }
@@ -64,6 +64,28 @@ impl IP {
         has_valid_ant && !ant_in_hyper
     }
 
+    // The Area-Broadcast Accessors in s, an "xyx" with x and y different, as
+    // their (x, y) pairs
+    fn area_broadcast_accessors(s: &str) -> Vec<(char, char)> {
+        let cs: Vec<char> = s.chars().collect();
+
+        cs.windows(3)
+            .filter(|w| w[0] == w[2] && w[0] != w[1])
+            .map(|w| (w[0], w[1]))
+            .collect()
+    }
+
+    pub fn supports_ssl(&self) -> bool {
+        // An ABA outside the hypernets, with its BAB in one of them
+        self.ant_seq
+            .iter()
+            .flat_map(|ant| IP::area_broadcast_accessors(ant))
+            .any(|(a, b)| {
+                let bab: String = [b, a, b].iter().collect();
+                self.hypernet_seq.iter().any(|hyp| hyp.contains(&bab))
+            })
+    }
+
     pub fn from_components(ants: Vec<String>, hypers: Vec<String>) -> IP {
         IP {
             ant_seq: ants,
@@ -71,7 +93,7 @@ impl IP {
         }
     }
 
-    pub fn from_string(ipstr: &str) -> Option<IP> {
+    pub fn from_string(ipstr: &str) -> Result<IP, ParseError> {
         let mut ants: Vec<String> = vec![];
         let mut hypers: Vec<String> = vec![];
 
@@ -79,8 +101,14 @@ impl IP {
         let mut current_hyper = String::new();
         let mut hyper_section = false;
 
-        for c in ipstr.chars() {
+        for (i, c) in ipstr.chars().enumerate() {
             match c {
+                '[' if hyper_section => {
+                    return Err(ParseError::new("[", "\"]\"").at(0, i + 1));
+                }
+                ']' if !hyper_section => {
+                    return Err(ParseError::new("]", "a letter or \"[\"").at(0, i + 1));
+                }
                 '[' => {
                     // start of a hypernet section
                     hyper_section = true;
@@ -112,42 +140,45 @@ impl IP {
             }
         }
 
+        if hyper_section {
+            return Err(ParseError::new("", "\"]\"").at(0, ipstr.chars().count() + 1));
+        }
+
         // We can have an ant section which we have not pushed in yet
         if !current_ant.is_empty() {
             ants.push(current_ant.to_owned());
         }
 
         if ants.len() > 0 && hypers.len() > 0 {
-            Some(IP::from_components(ants, hypers))
+            Ok(IP::from_components(ants, hypers))
         } else {
-            None
+            Err(ParseError::new(ipstr, "an address like \"abba[mnop]qrst\"").at(0, 1))
         }
     }
 }
 
-pub fn problem() {
-    let mut valid_count = 0;
+pub struct Day7;
 
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let sline = line.unwrap();
+impl Solution for Day7 {
+    type Input = Vec<IP>;
 
-        if let Some(ip) = IP::from_string(&sline) {
-            if ip.is_valid() {
-                valid_count += 1;
-            }
-        } else {
-            println!("Warning: {} is not a valid IP", sline);
-        }
+    fn parse(&self, input: &str) -> Result<Vec<IP>, ParseError> {
+        parse_lines(input, IP::from_string)
+    }
+
+    fn part1(&self, input: &Vec<IP>) -> Answer {
+        input.iter().filter(|ip| ip.is_valid()).count().into()
     }
 
-    println!("There are {} valid IPs", valid_count);
+    fn part2(&self, input: &Vec<IP>) -> Answer {
+        input.iter().filter(|ip| ip.supports_ssl()).count().into()
+    }
 }
 
 #[test]
 fn test_ip_from_string() {
     let ip_opt = IP::from_string("abba[mnop]qrst");
-    assert!(ip_opt.is_some());
+    assert!(ip_opt.is_ok());
 
     let ip = ip_opt.unwrap();
     assert_eq!(2, ip.ant_seq.len());
@@ -179,3 +210,23 @@ fn test_valid_annotation() {
     assert!(!IP::has_valid_annotation("XxyY"));
     assert!(!IP::has_valid_annotation("aaaa"));
 }
+
+#[test]
+fn test_supports_ssl() {
+    assert!(IP::from_string("aba[bab]xyz").unwrap().supports_ssl());
+    assert!(!IP::from_string("xyx[xyx]xyx").unwrap().supports_ssl());
+    assert!(IP::from_string("aaa[kek]eke").unwrap().supports_ssl());
+    assert!(IP::from_string("zazbz[bzb]cdb").unwrap().supports_ssl());
+}
+
+#[test]
+fn test_ip_parse_errors() {
+    assert_eq!(
+        Some(ParseError::new("", "\"]\"").at(0, 10)),
+        IP::from_string("abba[mnop").err()
+    );
+    assert_eq!(
+        Some(ParseError::new("[", "\"]\"").at(0, 8)),
+        IP::from_string("abba[mn[op]]qrst").err()
+    );
+}
@@ -0,0 +1,22 @@
+extern crate advent_core;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+
+use advent_core::Registry;
+
+// Add all of 2016's solutions to the given registry
+pub fn register(registry: &mut Registry) {
+    registry.register(2016, 1, "No Time for a Taxicab", day1::Day1);
+    registry.register(2016, 2, "Bathroom Security", day2::Day2);
+    registry.register(2016, 3, "Squares With Three Sides", day3::Day3);
+    registry.register(2016, 4, "Security Through Obscurity", day4::Day4);
+    registry.register(2016, 5, "How About a Nice Game of Chess?", day5::Day5);
+    registry.register(2016, 6, "Signals and Noise", day6::Day6);
+    registry.register(2016, 7, "Internet Protocol Version 7", day7::Day7);
+}
@@ -1,3 +1,26 @@
-pub fn problem() {
-    println!("This is day1");
+// https://adventofcode.com/2017/day/1
+/*
+--- Day 1: Inverse Captcha ---
+*/
+use advent_core::{parse_lines, Answer, ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        parse_lines(input, |line| Ok(line.to_owned()))
+    }
+
+    fn part1(&self, _input: &Vec<String>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[test]
+fn test_examples() {
+    // Check the examples from the puzzle description here
+    let input = Day1.parse("").unwrap();
+    assert_eq!(Answer::Unsolved, Day1.part1(&input));
 }
@@ -0,0 +1,10 @@
+extern crate advent_core;
+
+mod day1;
+
+use advent_core::Registry;
+
+// Add all of 2017's solutions to the given registry
+pub fn register(registry: &mut Registry) {
+    registry.register(2017, 1, "Inverse Captcha", day1::Day1);
+}
@@ -1,3 +0,0 @@
-pub fn main() {
-    println!("Advent of Code, 2017");
-}